@@ -0,0 +1,128 @@
+use bip39_rusty::{Language, Mnemonic};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
+use sha2::{Digest, Sha256};
+
+const BATCH_SIZE: usize = 1000; // Number of mnemonics created per iteration for bulk generation
+
+/*
+    Previous String based encoder, kept here only as a baseline for the bit-level encoder.
+    Entropy + checksum are turned into a String of '0'/'1' characters, split in 11 character chunks
+    and every chunk is parsed back with from_str_radix.
+*/
+fn legacy_phrase_from_entropy(entropy: &[u8], lang: &Language) -> Vec<String> {
+    let checksum_bits = entropy.len() * 8 / 32;
+    let checksum = Sha256::digest(entropy)[0] >> (8 - checksum_bits);
+
+    let mut binary_entropy = String::new();
+    for byte in entropy {
+        binary_entropy += &format!("{:08b}", byte);
+    }
+    binary_entropy += &format!("{:0width$b}", checksum, width = checksum_bits);
+
+    let wordlist = Language::get_predefined_word_list(lang);
+    let mut phrase = Vec::new();
+    let mut start_idx = 0;
+
+    while start_idx + 11 <= binary_entropy.len() {
+        let decimal = usize::from_str_radix(&binary_entropy[start_idx..start_idx + 11], 2).unwrap();
+        phrase.push(String::from(wordlist[decimal]));
+        start_idx += 11;
+    }
+    phrase
+}
+
+fn legacy_entropy_from_phrase(phrase: &str, lang: &Language) -> Vec<u8> {
+    let wordlist = Language::get_predefined_word_list(lang);
+    let mut binary_phrase = String::new();
+
+    for word in phrase.split_whitespace() {
+        let index = wordlist.iter().position(|candidate| *candidate == word).unwrap();
+        binary_phrase += &format!("{:011b}", index);
+    }
+
+    let entropy_bits = binary_phrase.len() * 32 / 33;
+    (0..entropy_bits)
+        .step_by(8)
+        .map(|start_idx| u8::from_str_radix(&binary_phrase[start_idx..start_idx + 8], 2).unwrap())
+        .collect()
+}
+
+fn random_entropies() -> Vec<[u8; 32]> {
+    let mut rng = ChaCha20Rng::from_seed([42u8; 32]);
+
+    (0..BATCH_SIZE)
+        .map(|_| {
+            let mut entropy = [0u8; 32];
+            rng.fill_bytes(&mut entropy);
+            entropy
+        })
+        .collect()
+}
+
+fn bench_bulk_generation(c: &mut Criterion) {
+    let entropies = random_entropies();
+    let mut group = c.benchmark_group("bulk_generation_24_words");
+
+    group.bench_function("legacy_binary_string", |b| {
+        b.iter(|| {
+            for entropy in &entropies {
+                black_box(legacy_phrase_from_entropy(black_box(entropy), &Language::English));
+            }
+        })
+    });
+
+    group.bench_function("bit_level", |b| {
+        b.iter(|| {
+            for entropy in &entropies {
+                black_box(Mnemonic::from_entropy(black_box(entropy), Language::English).unwrap());
+            }
+        })
+    });
+
+    group.bench_function("generate_with_rng", |b| {
+        b.iter_batched(
+            || ChaCha20Rng::from_seed([7u8; 32]),
+            |mut rng| {
+                for _ in 0..BATCH_SIZE {
+                    black_box(
+                        Mnemonic::generate_with_rng(&mut rng, Language::English, bip39_rusty::MnemonicType::Bits256).unwrap()
+                    );
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn bench_bulk_parsing(c: &mut Criterion) {
+    let phrases: Vec<String> = random_entropies()
+        .iter()
+        .map(|entropy| Mnemonic::from_entropy(entropy, Language::English).unwrap().mnemonic_phrase().join(" "))
+        .collect();
+    let mut group = c.benchmark_group("bulk_parsing_24_words");
+
+    group.bench_function("legacy_binary_string", |b| {
+        b.iter(|| {
+            for phrase in &phrases {
+                black_box(legacy_entropy_from_phrase(black_box(phrase), &Language::English));
+            }
+        })
+    });
+
+    group.bench_function("bit_level", |b| {
+        b.iter(|| {
+            for phrase in &phrases {
+                black_box(Mnemonic::from_phrase(black_box(phrase), Language::English).unwrap());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_bulk_generation, bench_bulk_parsing);
+criterion_main!(benches);
@@ -0,0 +1,434 @@
+/*
+    Account presets of BIP-44 (P2PKH), BIP-49 (P2SH-P2WPKH), BIP-84 (P2WPKH) and BIP-86 (P2TR).
+
+    Every preset derives the account key at m/purpose'/coin_type'/account' and the addresses at
+    .../change/index, only the address encoding and the SLIP-132 version bytes of the extended keys differ.
+*/
+
+use super::{hash160, parse_tweak, Bip32Error, ChildNumber, DerivationPath, ExtendedPrivKey, Network};
+use crate::Mnemonic;
+use alloc::string::String;
+use alloc::vec::Vec;
+use bech32::{segwit, Fe32, Hrp};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{ProjectivePoint, PublicKey};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+/// Address type of an account, the first level of its derivation path
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Purpose {
+    /// Legacy P2PKH addresses (1... on mainnet), m/44'
+    Bip44,
+    /// P2WPKH nested in P2SH (3... on mainnet), m/49', extended keys serialize as ypub/upub
+    Bip49,
+    /// Native SegWit P2WPKH in bech32 (bc1q... on mainnet), m/84', extended keys serialize as zpub/vpub
+    Bip84,
+    /// Single key Taproot P2TR in bech32m (bc1p... on mainnet), m/86'
+    Bip86,
+}
+
+impl Purpose {
+    pub const ALL: [Purpose; 4] = [Purpose::Bip44, Purpose::Bip49, Purpose::Bip84, Purpose::Bip86];
+
+    /// Number of the BIP, used as the hardened purpose index of the path
+    pub const fn number(&self) -> u32 {
+        match self {
+            Purpose::Bip44 => 44,
+            Purpose::Bip49 => 49,
+            Purpose::Bip84 => 84,
+            Purpose::Bip86 => 86,
+        }
+    }
+
+    /// Version bytes of serialized extended private keys, from SLIP-132
+    pub const fn private_version(&self, network: Network) -> [u8; 4] {
+        match (self, network) {
+            (Purpose::Bip44 | Purpose::Bip86, network) => network.private_version(),
+            (Purpose::Bip49, Network::Mainnet) => [0x04, 0x9d, 0x78, 0x78], // yprv
+            (Purpose::Bip49, _) => [0x04, 0x4a, 0x4e, 0x28], // uprv
+            (Purpose::Bip84, Network::Mainnet) => [0x04, 0xb2, 0x43, 0x0c], // zprv
+            (Purpose::Bip84, _) => [0x04, 0x5f, 0x18, 0xbc], // vprv
+        }
+    }
+
+    /// Version bytes of serialized extended public keys, from SLIP-132
+    pub const fn public_version(&self, network: Network) -> [u8; 4] {
+        match (self, network) {
+            (Purpose::Bip44 | Purpose::Bip86, network) => network.public_version(),
+            (Purpose::Bip49, Network::Mainnet) => [0x04, 0x9d, 0x7c, 0xb2], // ypub
+            (Purpose::Bip49, _) => [0x04, 0x4a, 0x52, 0x62], // upub
+            (Purpose::Bip84, Network::Mainnet) => [0x04, 0xb2, 0x47, 0x46], // zpub
+            (Purpose::Bip84, _) => [0x04, 0x5f, 0x1c, 0xf6], // vpub
+        }
+    }
+
+    /// Encodes the address paying to `public_key` on `network`
+    pub fn address(&self, public_key: &PublicKey, network: Network) -> String {
+        let key_hash = hash160(public_key.to_encoded_point(true).as_bytes());
+
+        match self {
+            Purpose::Bip44 => base58_address(network.p2pkh_prefix(), &key_hash),
+            Purpose::Bip49 => {
+                // redeemScript of P2WPKH: OP_0 <20-byte key hash>
+                let mut script = [0u8; 22];
+                script[..2].copy_from_slice(&[0x00, 0x14]);
+                script[2..].copy_from_slice(&key_hash);
+                base58_address(network.p2sh_prefix(), &hash160(&script))
+            }
+            Purpose::Bip84 => segwit_address(network, segwit::VERSION_0, &key_hash),
+            Purpose::Bip86 => segwit_address(network, segwit::VERSION_1, &taproot_output_key(public_key)),
+        }
+    }
+}
+
+impl Network {
+    /// Coin type of the BIP-44 path, 1 is shared by every test network
+    pub const fn coin_type(&self) -> u32 {
+        match self {
+            Network::Mainnet => 0,
+            Network::Testnet | Network::Regtest => 1,
+        }
+    }
+
+    const fn p2pkh_prefix(&self) -> u8 {
+        match self {
+            Network::Mainnet => 0x00,
+            Network::Testnet | Network::Regtest => 0x6f,
+        }
+    }
+
+    const fn p2sh_prefix(&self) -> u8 {
+        match self {
+            Network::Mainnet => 0x05,
+            Network::Testnet | Network::Regtest => 0xc4,
+        }
+    }
+
+    const fn bech32_hrp(&self) -> Hrp {
+        match self {
+            Network::Mainnet => bech32::hrp::BC,
+            Network::Testnet => bech32::hrp::TB,
+            Network::Regtest => bech32::hrp::BCRT,
+        }
+    }
+}
+
+/// Account key at m/purpose'/coin_type'/account' with its address type
+pub struct Account {
+    purpose: Purpose,
+    index: u32,
+    key: ExtendedPrivKey,
+}
+
+impl Account {
+    /// Derives account `index` from the master key, the coin type follows the network of the key
+    pub fn new(master: &ExtendedPrivKey, purpose: Purpose, index: u32) -> Result<Account, Bip32Error> {
+        let path = Self::path_of(purpose, master.network(), index)?;
+
+        Ok(Account { purpose, index, key: master.derive_path(&path)? })
+    }
+
+    /// Derives account `index` from the seed of a mnemonic and its passphrase
+    pub fn from_mnemonic(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        purpose: Purpose,
+        network: Network,
+        index: u32,
+    ) -> Result<Account, Bip32Error> {
+        Self::new(&ExtendedPrivKey::from_mnemonic(mnemonic, passphrase, network)?, purpose, index)
+    }
+
+    pub fn purpose(&self) -> Purpose {
+        self.purpose
+    }
+
+    pub fn network(&self) -> Network {
+        self.key.network()
+    }
+
+    /// Account number, the third level of the path
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Path of the account key, e.g. m/84'/0'/0'
+    pub fn path(&self) -> DerivationPath {
+        Self::path_of(self.purpose, self.network(), self.index).expect("Index is checked when the account is created")
+    }
+
+    /// Account private key, use `extended_private_key` for its xprv/yprv/zprv string
+    pub fn key(&self) -> &ExtendedPrivKey {
+        &self.key
+    }
+
+    /// Account public key as xpub/ypub/zpub (tpub/upub/vpub on test networks), for watch-only wallets
+    ///
+    /// `ExtendedPubKey::from_base58` reads it back, ypub/zpub keys with their purpose for `Purpose::address`.
+    pub fn extended_public_key(&self) -> String {
+        let version = self.purpose.public_version(self.network());
+        self.key.extended_public_key().to_base58_with_version(version)
+    }
+
+    /// Account private key as xprv/yprv/zprv (tprv/uprv/vprv on test networks), the string is wiped when dropped
+    pub fn extended_private_key(&self) -> Zeroizing<String> {
+        self.key.to_base58_with_version(self.purpose.private_version(self.network()))
+    }
+
+    /// Private key of the address at .../change/index, change addresses use the internal chain 1
+    pub fn address_key(&self, change: bool, index: u32) -> Result<ExtendedPrivKey, Bip32Error> {
+        self.key.derive_path(&Self::address_path(change, index)?)
+    }
+
+    /// Address at .../change/index, derived from the account public key only
+    pub fn address(&self, change: bool, index: u32) -> Result<String, Bip32Error> {
+        let key = self.key.extended_public_key().derive_path(&Self::address_path(change, index)?)?;
+        Ok(self.purpose.address(&key.public_key(), self.network()))
+    }
+
+    /// First `count` addresses of the external chain, the ones handed out to receive payments
+    pub fn receive_addresses(&self, count: u32) -> Result<Vec<String>, Bip32Error> {
+        self.addresses(false, count)
+    }
+
+    /// First `count` addresses of the internal chain, used by wallets for the change of their own transactions
+    pub fn change_addresses(&self, count: u32) -> Result<Vec<String>, Bip32Error> {
+        self.addresses(true, count)
+    }
+
+    fn addresses(&self, change: bool, count: u32) -> Result<Vec<String>, Bip32Error> {
+        // The chain key is derived once, every address is a single CKDpub step from it
+        let chain = self.key.extended_public_key().derive_child(ChildNumber::Normal(change as u32))?;
+
+        (0..count)
+            .map(|index| {
+                let key = chain.derive_child(ChildNumber::normal(index)?)?;
+                Ok(self.purpose.address(&key.public_key(), self.network()))
+            })
+            .collect()
+    }
+
+    fn path_of(purpose: Purpose, network: Network, index: u32) -> Result<DerivationPath, Bip32Error> {
+        Ok(DerivationPath::from(alloc::vec![
+            ChildNumber::Hardened(purpose.number()),
+            ChildNumber::Hardened(network.coin_type()),
+            ChildNumber::hardened(index)?,
+        ]))
+    }
+
+    fn address_path(change: bool, index: u32) -> Result<DerivationPath, Bip32Error> {
+        Ok(DerivationPath::from(alloc::vec![ChildNumber::Normal(change as u32), ChildNumber::normal(index)?]))
+    }
+}
+
+fn base58_address(prefix: u8, hash: &[u8; 20]) -> String {
+    let mut payload = [0u8; 21];
+    payload[0] = prefix;
+    payload[1..].copy_from_slice(hash);
+    bs58::encode(payload).with_check().into_string()
+}
+
+fn segwit_address(network: Network, version: Fe32, program: &[u8]) -> String {
+    segwit::encode(network.bech32_hrp(), version, program).expect("Witness programs of 20 and 32 bytes are valid")
+}
+
+/// Taproot output key of BIP-86: the internal key tweaked with its own tagged hash, no script path
+fn taproot_output_key(public_key: &PublicKey) -> [u8; 32] {
+    let encoded = public_key.to_encoded_point(true);
+    let x_only = &encoded.as_bytes()[1..];
+
+    // BIP-340 keys are x-only, the internal key is the point with an even y
+    let internal = match encoded.as_bytes()[0] {
+        0x03 => -public_key.to_projective(),
+        _ => public_key.to_projective(),
+    };
+
+    let tag = Sha256::digest(b"TapTweak");
+    let tweak_hash = Sha256::new().chain_update(tag).chain_update(tag).chain_update(x_only).finalize();
+    // Fails with probability below 2^-127, like an invalid child key
+    let tweak = parse_tweak(&tweak_hash).expect("TapTweak hash is below the curve order");
+
+    let output = (internal + ProjectivePoint::GENERATOR * tweak).to_affine();
+    let encoded = output.to_encoded_point(true);
+    encoded.as_bytes()[1..].try_into().expect("x coordinate is 32 bytes")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+    use crate::bip32::ExtendedPubKey;
+    use alloc::string::ToString;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn abandon_account(purpose: Purpose, network: Network) -> Account {
+        let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).unwrap();
+        Account::from_mnemonic(&mnemonic, "", purpose, network, 0).unwrap()
+    }
+
+    #[test]
+    fn test_bip44_account() {
+        let account = abandon_account(Purpose::Bip44, Network::Mainnet);
+
+        assert_eq!(account.path().to_string(), "m/44'/0'/0'");
+        assert_eq!(
+            account.extended_public_key(),
+            "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj"
+        );
+        assert_eq!(
+            account.extended_private_key().as_str(),
+            "xprv9xpXFhFpqdQK3TmytPBqXtGSwS3DLjojFhTGht8gwAAii8py5X6pxeBnQ6ehJiyJ6nDjWGJfZ95WxByFXVkDxHXrqu53WCRGypk2ttuqncb"
+        );
+        assert_eq!(
+            account.receive_addresses(2).unwrap(),
+            ["1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA", "1Ak8PffB2meyfYnbXZR9EGfLfFZVpzJvQP"]
+        );
+        assert_eq!(
+            account.change_addresses(2).unwrap(),
+            ["1J3J6EvPrv8q6AC3VCjWV45Uf3nssNMRtH", "13vKxXzHXXd8HquAYdpkJoi9ULVXUgfpS5"]
+        );
+        assert_eq!(abandon_account(Purpose::Bip44, Network::Testnet).address(false, 0).unwrap(), "mkpZhYtJu2r87Js3pDiWJDmPte2NRZ8bJV");
+    }
+
+    #[test]
+    fn test_bip49_vectors() {
+        // Test vector of BIP-49, on testnet
+        let account = abandon_account(Purpose::Bip49, Network::Testnet);
+
+        assert_eq!(account.path().to_string(), "m/49'/1'/0'");
+        assert_eq!(
+            account.key().to_base58().as_str(),
+            "tprv8gRrNu65W2Msef2BdBSUgFdRTGzC8EwVXnV7UGS3faeXtuMVtGfEdidVeGbThs4ELEoayCAzZQ4uUji9DUiAs7erdVskqju7hrBcDvDsdbY"
+        );
+        assert_eq!(
+            account.extended_public_key(),
+            "upub5EFU65HtV5TeiSHmZZm7FUffBGy8UKeqp7vw43jYbvZPpoVsgU93oac7Wk3u6moKegAEWtGNF8DehrnHtv21XXEMYRUocHqguyjknFHYfgY"
+        );
+        assert_eq!(
+            account.extended_private_key().as_str(),
+            "uprv91G7gZkzehuMVxDJTYE6tLivdF8e4rvzSu1LFfKw3b2Qx1Aj8vpoFnHdfUZ3hmi9jsvPifmZ24RTN2KhwB8BfMLTVqaBReibyaFFcTP1s9n"
+        );
+        assert_eq!(account.address(false, 0).unwrap(), "2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2");
+        assert_eq!(account.receive_addresses(2).unwrap()[1], "2N55m54k8vr95ggehfUcNkdbUuQvaqG2GxK");
+        assert_eq!(account.change_addresses(1).unwrap(), ["2MvdUi5o3f2tnEFh9yGvta6FzptTZtkPJC8"]);
+
+        let mainnet = abandon_account(Purpose::Bip49, Network::Mainnet);
+        assert_eq!(
+            mainnet.extended_public_key(),
+            "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP"
+        );
+        assert_eq!(mainnet.receive_addresses(2).unwrap(), ["37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf", "3LtMnn87fqUeHBUG414p9CWwnoV6E2pNKS"]);
+    }
+
+    #[test]
+    fn test_bip84_vectors() {
+        let account = abandon_account(Purpose::Bip84, Network::Mainnet);
+
+        assert_eq!(account.path().to_string(), "m/84'/0'/0'");
+        assert_eq!(
+            account.extended_private_key().as_str(),
+            "zprvAdG4iTXWBoARxkkzNpNh8r6Qag3irQB8PzEMkAFeTRXxHpbF9z4QgEvBRmfvqWvGp42t42nvgGpNgYSJA9iefm1yYNZKEm7z6qUWCroSQnE"
+        );
+        assert_eq!(
+            account.extended_public_key(),
+            "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs"
+        );
+        assert_eq!(
+            account.receive_addresses(2).unwrap(),
+            ["bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu", "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g"]
+        );
+        assert_eq!(
+            account.change_addresses(1).unwrap(),
+            ["bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el"]
+        );
+
+        let testnet = abandon_account(Purpose::Bip84, Network::Testnet);
+        assert_eq!(
+            testnet.extended_public_key(),
+            "vpub5Y6cjg78GGuNLsaPhmYsiw4gYX3HoQiRBiSwDaBXKUafCt9bNwWQiitDk5VZ5BVxYnQdwoTyXSs2JHRPAgjAvtbBrf8ZhDYe2jWAqvZVnsc"
+        );
+        assert_eq!(testnet.address(false, 0).unwrap(), "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl");
+
+        // Regtest shares the coin type and versions of testnet, only the bech32 prefix differs
+        let regtest = abandon_account(Purpose::Bip84, Network::Regtest);
+        assert_eq!(regtest.path().to_string(), "m/84'/1'/0'");
+        assert_eq!(regtest.address(false, 0).unwrap(), "bcrt1q6rz28mcfaxtmd6v789l9rrlrusdprr9pz3cppk");
+    }
+
+    #[test]
+    fn test_bip86_vectors() {
+        let account = abandon_account(Purpose::Bip86, Network::Mainnet);
+
+        assert_eq!(
+            account.extended_private_key().as_str(),
+            "xprv9xgqHN7yz9MwCkxsBPN5qetuNdQSUttZNKw1dcYTV4mkaAFiBVGQziHs3NRSWMkCzvgjEe3n9xV8oYywvM8at9yRqyaZVz6TYYhX98VjsUk"
+        );
+        assert_eq!(
+            account.extended_public_key(),
+            "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ"
+        );
+        assert_eq!(
+            account.receive_addresses(2).unwrap(),
+            [
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+                "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+            ]
+        );
+        assert_eq!(
+            account.change_addresses(1).unwrap(),
+            ["bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7"]
+        );
+    }
+
+    #[test]
+    fn test_extended_keys_round_trip() {
+        for (purpose, recorded) in [
+            (Purpose::Bip44, None),
+            (Purpose::Bip49, Some(Purpose::Bip49)),
+            (Purpose::Bip84, Some(Purpose::Bip84)),
+            (Purpose::Bip86, None),
+        ] {
+            for network in [Network::Mainnet, Network::Testnet] {
+                let account = abandon_account(purpose, network);
+
+                // A watch-only wallet only gets the account xpub/ypub/zpub
+                let xpub = ExtendedPubKey::from_base58(&account.extended_public_key()).unwrap();
+                assert_eq!((xpub.network(), xpub.purpose()), (network, recorded), "{:?} {:?}", purpose, network);
+                assert_eq!(xpub.to_base58(), account.extended_public_key());
+                assert_eq!(xpub.public_key(), account.key().public_key());
+
+                let key = xpub.derive_path(&"m/0/1".parse().unwrap()).unwrap();
+                assert_eq!(purpose.address(&key.public_key(), network), account.address(false, 1).unwrap());
+
+                let xprv = ExtendedPrivKey::from_base58(&account.extended_private_key()).unwrap();
+                assert_eq!((xprv.network(), xprv.purpose()), (network, recorded));
+                assert_eq!(xprv.to_base58(), account.extended_private_key());
+                assert_eq!(xprv.extended_public_key(), xpub);
+            }
+        }
+    }
+
+    #[test]
+    fn test_address_key_matches_address() {
+        for purpose in Purpose::ALL {
+            let account = abandon_account(purpose, Network::Mainnet);
+            let key = account.address_key(true, 3).unwrap();
+
+            assert_eq!(purpose.address(&key.public_key(), Network::Mainnet), account.address(true, 3).unwrap());
+            assert_eq!(key.depth(), 5);
+        }
+    }
+
+    #[test]
+    fn test_hardened_indexes_are_rejected() {
+        let master = ExtendedPrivKey::new_master(Network::Mainnet, &[1; 32]).unwrap();
+
+        assert!(matches!(Account::new(&master, Purpose::Bip84, 1 << 31), Err(Bip32Error::InvalidIndex(_))));
+        let account = Account::new(&master, Purpose::Bip84, 1).unwrap();
+        assert_eq!(account.path().to_string(), "m/84'/0'/1'");
+        assert!(matches!(account.address(false, u32::MAX), Err(Bip32Error::InvalidIndex(u32::MAX))));
+    }
+}
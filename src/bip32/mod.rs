@@ -0,0 +1,793 @@
+/*
+    BIP-32 hierarchical deterministic keys built from the BIP-39 seed.
+
+    The master key is HMAC-SHA512 of the seed keyed with "Bitcoin seed": the left 32 bytes are the private key,
+    the right 32 bytes the chain code. Children are derived with HMAC-SHA512 keyed with the chain code of the parent
+    (CKDpriv / CKDpub). Extended keys are serialized in 78 bytes
+    (version, depth, parent fingerprint, child number, chain code, key) and encoded with Base58Check.
+*/
+
+mod account;
+mod path;
+
+pub use account::{Account, Purpose};
+pub use path::{ChildNumber, DerivationPath};
+
+use crate::Mnemonic;
+use alloc::string::String;
+use hmac::{Hmac, Mac};
+use k256::elliptic_curve::group::prime::PrimeCurveAffine;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+use k256::{FieldBytes, NonZeroScalar, ProjectivePoint, PublicKey, Scalar, SecretKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::{Zeroize, Zeroizing};
+
+const MASTER_KEY_SALT: &[u8] = b"Bitcoin seed";
+const SERIALIZED_LENGTH: usize = 78;
+
+// Network and SLIP-132 purpose of the version bytes of a serialized key
+type KeyVersion = (Network, Option<Purpose>);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Network {
+    /// Bitcoin mainnet, keys serialize as xprv
+    Mainnet,
+    /// Bitcoin testnet, keys serialize as tprv
+    Testnet,
+    /// Local regression test network, keys serialize as tprv like testnet
+    Regtest,
+}
+
+impl Network {
+    /// Version bytes of a serialized extended private key
+    pub const fn private_version(&self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => [0x04, 0x88, 0xad, 0xe4], // xprv
+            Network::Testnet | Network::Regtest => [0x04, 0x35, 0x83, 0x94], // tprv
+        }
+    }
+
+    /// Version bytes of a serialized extended public key
+    pub const fn public_version(&self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => [0x04, 0x88, 0xb2, 0x1e], // xpub
+            Network::Testnet | Network::Regtest => [0x04, 0x35, 0x87, 0xcf], // tpub
+        }
+    }
+
+    // Regtest shares the testnet versions, so parsed tprv/tpub keys are always Testnet
+    fn from_private_version(version: [u8; 4]) -> Option<KeyVersion> {
+        Self::from_version(version, |network, purpose| match purpose {
+            Some(purpose) => purpose.private_version(network),
+            None => network.private_version(),
+        })
+    }
+
+    fn from_public_version(version: [u8; 4]) -> Option<KeyVersion> {
+        Self::from_version(version, |network, purpose| match purpose {
+            Some(purpose) => purpose.public_version(network),
+            None => network.public_version(),
+        })
+    }
+
+    // Plain BIP-32 versions first, then the SLIP-132 versions of BIP-49 and BIP-84 keys
+    fn from_version(version: [u8; 4], version_of: fn(Network, Option<Purpose>) -> [u8; 4]) -> Option<KeyVersion> {
+        [None, Some(Purpose::Bip49), Some(Purpose::Bip84)]
+            .into_iter()
+            .flat_map(|purpose| [(Network::Mainnet, purpose), (Network::Testnet, purpose)])
+            .find(|(network, purpose)| version_of(*network, *purpose) == version)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Bip32Error {
+    /// Seed length in bytes is not between 16 and 64
+    BadSeedLength(usize),
+    /// Private key is not in the range 1 to n-1 of secp256k1, or its 0x00 prefix is missing
+    InvalidPrivateKey,
+    /// Extended key contains characters outside of the Base58 alphabet
+    InvalidBase58,
+    /// Base58Check checksum of the extended key doesn't match its payload
+    InvalidChecksum,
+    /// Decoded extended key is not 78 bytes long
+    BadLength(usize),
+    /// Version bytes don't belong to a supported network and key type
+    UnknownVersion([u8; 4]),
+    /// Master key (depth 0) with a non-zero parent fingerprint or child number
+    InvalidMasterFields,
+    /// Public key is not a compressed point of secp256k1
+    InvalidPublicKey,
+    /// Hardened child can't be derived from an extended public key
+    HardenedFromPublic(ChildNumber),
+    /// Derived child key is invalid (probability below 2^-127), the next index should be used instead
+    InvalidChildKey(ChildNumber),
+    /// Derivation path or child number can't be parsed
+    InvalidPath(String),
+    /// Child index is 2^31 or more, outside the range of both normal and hardened indexes
+    InvalidIndex(u32),
+    /// Key is already 255 levels deep, the maximum depth of a serialized key
+    MaximumDepth,
+}
+
+impl core::fmt::Display for Bip32Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Bip32Error::BadSeedLength(bytes) => {
+                write!(f, "Invalid seed length: expected 16 to 64 bytes, but got {} bytes.", bytes)
+            }
+            Bip32Error::InvalidPrivateKey => write!(f, "Invalid private key for secp256k1."),
+            Bip32Error::InvalidBase58 => write!(f, "Extended key is not valid Base58."),
+            Bip32Error::InvalidChecksum => write!(f, "Invalid Base58Check checksum of the extended key."),
+            Bip32Error::BadLength(bytes) => {
+                write!(f, "Invalid extended key length: expected 78 bytes, but got {} bytes.", bytes)
+            }
+            Bip32Error::UnknownVersion(version) => write!(f, "Unknown extended key version {:02x?}.", version),
+            Bip32Error::InvalidMasterFields => {
+                write!(f, "Master key must have a zero parent fingerprint and child number.")
+            }
+            Bip32Error::InvalidPublicKey => write!(f, "Invalid public key for secp256k1."),
+            Bip32Error::HardenedFromPublic(child) => {
+                write!(f, "Hardened child {} can't be derived from a public key.", child)
+            }
+            Bip32Error::InvalidChildKey(child) => write!(f, "Child {} is an invalid key, use the next index.", child),
+            Bip32Error::InvalidPath(path) => write!(f, "Invalid derivation path \"{}\".", path),
+            Bip32Error::InvalidIndex(index) => write!(f, "Index {} is above the maximum of 2^31 - 1.", index),
+            Bip32Error::MaximumDepth => write!(f, "Maximum depth of 255 derivations reached."),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Bip32Error {}
+
+/// Extended private key: a secp256k1 private key with its chain code and position in the key tree
+pub struct ExtendedPrivKey {
+    network: Network,
+    purpose: Option<Purpose>,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+    private_key: SecretKey, // wiped on drop by k256
+}
+
+impl Drop for ExtendedPrivKey {
+    /// Wipes the chain code, anyone holding it and the public key can derive the non-hardened children
+    fn drop(&mut self) {
+        self.chain_code.zeroize();
+    }
+}
+
+impl ExtendedPrivKey {
+    /// Builds the master key (depth 0) from a BIP-39 seed
+    pub fn new_master(network: Network, seed: &[u8]) -> Result<ExtendedPrivKey, Bip32Error> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(Bip32Error::BadSeedLength(seed.len()));
+        }
+
+        let mut hmac = Hmac::<Sha512>::new_from_slice(MASTER_KEY_SALT).expect("HMAC accepts keys of any length");
+        hmac.update(seed);
+        let output = Zeroizing::new(<[u8; 64]>::from(hmac.finalize().into_bytes()));
+
+        Ok(ExtendedPrivKey {
+            network,
+            purpose: None,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+            chain_code: output[32..].try_into().expect("Right half of HMAC-SHA512 is 32 bytes"),
+            private_key: Self::parse_private_key(&output[..32])?,
+        })
+    }
+
+    /// Builds the master key from the seed of a mnemonic and its passphrase
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str, network: Network) -> Result<ExtendedPrivKey, Bip32Error> {
+        Self::new_master(network, &*mnemonic.to_seed(passphrase))
+    }
+
+    /// Parses an xprv/tprv string, or the SLIP-132 yprv/zprv (uprv/vprv) of BIP-49/84 accounts
+    pub fn from_base58(encoded: &str) -> Result<ExtendedPrivKey, Bip32Error> {
+        let data = decode_base58_check(encoded)?;
+        let header = Header::parse(&data, Network::from_private_version)?;
+
+        // Private keys are prefixed with 0x00 to match the 33 bytes of a compressed public key
+        if data[45] != 0 {
+            return Err(Bip32Error::InvalidPrivateKey);
+        }
+
+        Ok(ExtendedPrivKey {
+            network: header.network,
+            purpose: header.purpose,
+            depth: header.depth,
+            parent_fingerprint: header.parent_fingerprint,
+            child_number: header.child_number,
+            chain_code: header.chain_code,
+            private_key: Self::parse_private_key(&data[46..])?,
+        })
+    }
+
+    /// Serializes the key as xprv (mainnet) or tprv (testnet), or yprv/zprv (uprv/vprv) for a BIP-49/84 purpose
+    ///
+    /// The string is wiped when dropped.
+    pub fn to_base58(&self) -> Zeroizing<String> {
+        match self.purpose {
+            Some(purpose) => self.to_base58_with_version(purpose.private_version(self.network)),
+            None => self.to_base58_with_version(self.network.private_version()),
+        }
+    }
+
+    /// Serializes the key with other version bytes, e.g. the SLIP-132 yprv/zprv of BIP-49/84 accounts
+    pub(crate) fn to_base58_with_version(&self, version: [u8; 4]) -> Zeroizing<String> {
+        let mut key = Zeroizing::new([0u8; 33]);
+        key[1..].copy_from_slice(&self.private_key.to_bytes());
+
+        let header = Header {
+            network: self.network,
+            purpose: self.purpose,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+        };
+        Zeroizing::new(encode_base58_check(version, &header, &key))
+    }
+
+    /// Derives a child key (CKDpriv), hardened or not
+    pub fn derive_child(&self, child: ChildNumber) -> Result<ExtendedPrivKey, Bip32Error> {
+        let index = child.checked_index()?;
+        let depth = self.depth.checked_add(1).ok_or(Bip32Error::MaximumDepth)?;
+
+        let mut hmac = Hmac::<Sha512>::new_from_slice(&self.chain_code).expect("HMAC accepts keys of any length");
+        if child.is_hardened() {
+            // Hardened children hash the private key, so they can't be derived from the public key
+            hmac.update(&[0]);
+            hmac.update(&self.private_key.to_bytes());
+        } else {
+            hmac.update(&self.public_key_bytes());
+        }
+        hmac.update(&index.to_be_bytes());
+        let output = Zeroizing::new(<[u8; 64]>::from(hmac.finalize().into_bytes()));
+
+        // Child key is IL + k (mod n), invalid if IL >= n or the sum is 0
+        let tweak = Zeroizing::new(parse_tweak(&output[..32]).ok_or(Bip32Error::InvalidChildKey(child))?);
+        let sum = Zeroizing::new(*tweak + *self.private_key.to_nonzero_scalar());
+        let child_key = Option::<NonZeroScalar>::from(NonZeroScalar::new(*sum)).ok_or(Bip32Error::InvalidChildKey(child))?;
+
+        Ok(ExtendedPrivKey {
+            network: self.network,
+            purpose: self.purpose,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            chain_code: output[32..].try_into().expect("Right half of HMAC-SHA512 is 32 bytes"),
+            private_key: SecretKey::from(child_key),
+        })
+    }
+
+    /// Derives the key at `path`, relative to this key
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPrivKey, Bip32Error> {
+        path.iter().try_fold(self.clone_key(), |key, child| key.derive_child(*child))
+    }
+
+    /// Extended public key with the same chain code, safe to hand to watch-only services
+    pub fn extended_public_key(&self) -> ExtendedPubKey {
+        ExtendedPubKey {
+            network: self.network,
+            purpose: self.purpose,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            public_key: self.public_key(),
+        }
+    }
+
+    /// First 4 bytes of the identifier, the master fingerprint when called on the master key
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let identifier = self.identifier();
+        [identifier[0], identifier[1], identifier[2], identifier[3]]
+    }
+
+    /// HASH160 (RIPEMD-160 of SHA-256) of the compressed public key
+    pub fn identifier(&self) -> [u8; 20] {
+        hash160(&self.public_key_bytes())
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.private_key.public_key()
+    }
+
+    /// Compressed SEC1 encoding of the public key (33 bytes)
+    pub fn public_key_bytes(&self) -> [u8; 33] {
+        let point = self.public_key().to_encoded_point(true);
+        point.as_bytes().try_into().expect("Compressed point is 33 bytes")
+    }
+
+    /// Raw 32-byte private key, wiped when dropped
+    pub fn private_key_bytes(&self) -> Zeroizing<[u8; 32]> {
+        Zeroizing::new(self.private_key.to_bytes().into())
+    }
+
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Purpose recorded by SLIP-132 version bytes: Bip49 for yprv/uprv, Bip84 for zprv/vprv keys
+    ///
+    /// None for xprv/tprv keys, which BIP-44, BIP-86 and custom paths share.
+    pub fn purpose(&self) -> Option<Purpose> {
+        self.purpose
+    }
+
+    /// Number of derivations from the master key, 0 for the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Index of this key in its parent, hardened indexes have the top bit set
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    fn parse_private_key(bytes: &[u8]) -> Result<SecretKey, Bip32Error> {
+        // Rejects 0 and values >= n, the order of secp256k1
+        SecretKey::from_bytes(FieldBytes::from_slice(bytes)).map_err(|_| Bip32Error::InvalidPrivateKey)
+    }
+
+    fn clone_key(&self) -> ExtendedPrivKey {
+        // Not a public Clone impl, copies of private keys should be explicit
+        ExtendedPrivKey {
+            network: self.network,
+            purpose: self.purpose,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            private_key: self.private_key.clone(),
+        }
+    }
+}
+
+/// Extended public key: derives the non-hardened children of a key without access to the private key
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedPubKey {
+    network: Network,
+    purpose: Option<Purpose>,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+    public_key: PublicKey,
+}
+
+impl ExtendedPubKey {
+    /// Parses an xpub/tpub string, or the SLIP-132 ypub/zpub (upub/vpub) of BIP-49/84 accounts
+    pub fn from_base58(encoded: &str) -> Result<ExtendedPubKey, Bip32Error> {
+        let data = decode_base58_check(encoded)?;
+        let header = Header::parse(&data, Network::from_public_version)?;
+
+        // Only compressed points (0x02 or 0x03 prefix) are allowed
+        if data[45] != 0x02 && data[45] != 0x03 {
+            return Err(Bip32Error::InvalidPublicKey);
+        }
+
+        Ok(ExtendedPubKey {
+            network: header.network,
+            purpose: header.purpose,
+            depth: header.depth,
+            parent_fingerprint: header.parent_fingerprint,
+            child_number: header.child_number,
+            chain_code: header.chain_code,
+            public_key: PublicKey::from_sec1_bytes(&data[45..]).map_err(|_| Bip32Error::InvalidPublicKey)?,
+        })
+    }
+
+    /// Serializes the key as xpub (mainnet) or tpub (testnet), or ypub/zpub (upub/vpub) for a BIP-49/84 purpose
+    pub fn to_base58(&self) -> String {
+        match self.purpose {
+            Some(purpose) => self.to_base58_with_version(purpose.public_version(self.network)),
+            None => self.to_base58_with_version(self.network.public_version()),
+        }
+    }
+
+    /// Serializes the key with other version bytes, e.g. the SLIP-132 ypub/zpub of BIP-49/84 accounts
+    pub(crate) fn to_base58_with_version(&self, version: [u8; 4]) -> String {
+        let header = Header {
+            network: self.network,
+            purpose: self.purpose,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+        };
+        encode_base58_check(version, &header, &self.public_key_bytes())
+    }
+
+    /// Derives a non-hardened child key (CKDpub)
+    pub fn derive_child(&self, child: ChildNumber) -> Result<ExtendedPubKey, Bip32Error> {
+        let index = child.checked_index()?;
+        if child.is_hardened() {
+            return Err(Bip32Error::HardenedFromPublic(child));
+        }
+        let depth = self.depth.checked_add(1).ok_or(Bip32Error::MaximumDepth)?;
+
+        let mut hmac = Hmac::<Sha512>::new_from_slice(&self.chain_code).expect("HMAC accepts keys of any length");
+        hmac.update(&self.public_key_bytes());
+        hmac.update(&index.to_be_bytes());
+        let output = <[u8; 64]>::from(hmac.finalize().into_bytes());
+
+        // Child key is IL*G + K, invalid if IL >= n or the sum is the point at infinity
+        let tweak = parse_tweak(&output[..32]).ok_or(Bip32Error::InvalidChildKey(child))?;
+        let point = (ProjectivePoint::GENERATOR * tweak + self.public_key.to_projective()).to_affine();
+        if bool::from(point.is_identity()) {
+            return Err(Bip32Error::InvalidChildKey(child));
+        }
+
+        Ok(ExtendedPubKey {
+            network: self.network,
+            purpose: self.purpose,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            chain_code: output[32..].try_into().expect("Right half of HMAC-SHA512 is 32 bytes"),
+            public_key: PublicKey::from_affine(point).map_err(|_| Bip32Error::InvalidChildKey(child))?,
+        })
+    }
+
+    /// Derives the key at `path`, relative to this key, every child must be non-hardened
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPubKey, Bip32Error> {
+        path.iter().try_fold(self.clone(), |key, child| key.derive_child(*child))
+    }
+
+    /// First 4 bytes of the identifier
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let identifier = self.identifier();
+        [identifier[0], identifier[1], identifier[2], identifier[3]]
+    }
+
+    /// HASH160 (RIPEMD-160 of SHA-256) of the compressed public key
+    pub fn identifier(&self) -> [u8; 20] {
+        hash160(&self.public_key_bytes())
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    /// Compressed SEC1 encoding of the public key (33 bytes)
+    pub fn public_key_bytes(&self) -> [u8; 33] {
+        let point = self.public_key.to_encoded_point(true);
+        point.as_bytes().try_into().expect("Compressed point is 33 bytes")
+    }
+
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Purpose recorded by SLIP-132 version bytes: Bip49 for ypub/upub, Bip84 for zpub/vpub keys
+    ///
+    /// None for xpub/tpub keys, which BIP-44, BIP-86 and custom paths share.
+    pub fn purpose(&self) -> Option<Purpose> {
+        self.purpose
+    }
+
+    /// Number of derivations from the master key, 0 for the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Index of this key in its parent, hardened indexes have the top bit set
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+}
+
+impl From<&ExtendedPrivKey> for ExtendedPubKey {
+    fn from(key: &ExtendedPrivKey) -> ExtendedPubKey {
+        key.extended_public_key()
+    }
+}
+
+/// Fields shared by serialized private and public extended keys, everything but the version and the key
+struct Header {
+    network: Network,
+    purpose: Option<Purpose>,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+}
+
+impl Drop for Header {
+    fn drop(&mut self) {
+        self.chain_code.zeroize();
+    }
+}
+
+impl Header {
+    fn parse(data: &[u8; SERIALIZED_LENGTH], network: fn([u8; 4]) -> Option<KeyVersion>) -> Result<Header, Bip32Error> {
+        let version: [u8; 4] = data[..4].try_into().expect("Version is 4 bytes");
+        let (network, purpose) = network(version).ok_or(Bip32Error::UnknownVersion(version))?;
+
+        let depth = data[4];
+        let parent_fingerprint: [u8; 4] = data[5..9].try_into().expect("Fingerprint is 4 bytes");
+        let child_number = u32::from_be_bytes(data[9..13].try_into().expect("Child number is 4 bytes"));
+        if depth == 0 && (parent_fingerprint != [0; 4] || child_number != 0) {
+            return Err(Bip32Error::InvalidMasterFields);
+        }
+
+        Ok(Header {
+            network,
+            purpose,
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code: data[13..45].try_into().expect("Chain code is 32 bytes"),
+        })
+    }
+}
+
+/// RIPEMD-160 of SHA-256, used for key identifiers and fingerprints
+pub(crate) fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+/// Parses the left half of a derivation HMAC as a scalar, None if it is not below n
+fn parse_tweak(bytes: &[u8]) -> Option<Scalar> {
+    Option::from(Scalar::from_repr(*FieldBytes::from_slice(bytes)))
+}
+
+fn encode_base58_check(version: [u8; 4], header: &Header, key: &[u8; 33]) -> String {
+    let mut data = Zeroizing::new([0u8; SERIALIZED_LENGTH]);
+
+    data[..4].copy_from_slice(&version);
+    data[4] = header.depth;
+    data[5..9].copy_from_slice(&header.parent_fingerprint);
+    data[9..13].copy_from_slice(&header.child_number.to_be_bytes());
+    data[13..45].copy_from_slice(&header.chain_code);
+    data[45..].copy_from_slice(key);
+
+    bs58::encode(&data[..]).with_check().into_string()
+}
+
+fn decode_base58_check(encoded: &str) -> Result<Zeroizing<[u8; SERIALIZED_LENGTH]>, Bip32Error> {
+    let decoded = Zeroizing::new(bs58::decode(encoded).with_check(None).into_vec().map_err(|e| match e {
+        bs58::decode::Error::InvalidChecksum { .. } => Bip32Error::InvalidChecksum,
+        _ => Bip32Error::InvalidBase58,
+    })?);
+
+    let mut data = Zeroizing::new([0u8; SERIALIZED_LENGTH]);
+    if decoded.len() != SERIALIZED_LENGTH {
+        return Err(Bip32Error::BadLength(decoded.len()));
+    }
+    data.copy_from_slice(&decoded);
+    Ok(data)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+
+    // (seed, master xprv, master fingerprint) of BIP-32 test vectors 1 to 4
+    const MASTER_VECTORS: [(&str, &str, &str); 4] = [
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            "3442193e",
+        ),
+        (
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+            "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+            "bd16bee5",
+        ),
+        (
+            "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be",
+            "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6",
+            "41d63b50",
+        ),
+        (
+            "3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678",
+            "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv",
+            "ad85d955",
+        ),
+    ];
+
+    // (path, xpub, xprv) of every chain of BIP-32 test vectors 1 to 4, the seeds are in MASTER_VECTORS
+    const CHAIN_VECTORS: [&[(&str, &str, &str)]; 4] = [
+        &[
+            ("m", "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8", "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi"),
+            ("m/0'", "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw", "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7"),
+            ("m/0'/1", "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ", "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs"),
+            ("m/0'/1/2'", "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5", "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM"),
+            ("m/0'/1/2'/2", "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV", "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334"),
+            ("m/0'/1/2'/2/1000000000", "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy", "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76"),
+        ],
+        &[
+            ("m", "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB", "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U"),
+            ("m/0", "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH", "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt"),
+            ("m/0/2147483647'", "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a", "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9"),
+            ("m/0/2147483647'/1", "xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon", "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef"),
+            ("m/0/2147483647'/1/2147483646'", "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL", "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc"),
+            ("m/0/2147483647'/1/2147483646'/2", "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt", "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j"),
+        ],
+        &[
+            ("m", "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13", "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6"),
+            ("m/0'", "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y", "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L"),
+        ],
+        &[
+            ("m", "xpub661MyMwAqRbcGczjuMoRm6dXaLDEhW1u34gKenbeYqAix21mdUKJyuyu5F1rzYGVxyL6tmgBUAEPrEz92mBXjByMRiJdba9wpnN37RLLAXa", "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv"),
+            ("m/0'", "xpub69AUMk3qDBi3uW1sXgjCmVjJ2G6WQoYSnNHyzkmdCHEhSZ4tBok37xfFEqHd2AddP56Tqp4o56AePAgCjYdvpW2PU2jbUPFKsav5ut6Ch1m", "xprv9vB7xEWwNp9kh1wQRfCCQMnZUEG21LpbR9NPCNN1dwhiZkjjeGRnaALmPXCX7SgjFTiCTT6bXes17boXtjq3xLpcDjzEuGLQBM5ohqkao9G"),
+            ("m/0'/1'", "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt", "xprv9xJocDuwtYCMNAo3Zw76WENQeAS6WGXQ55RCy7tDJ8oALr4FWkuVoHJeHVAcAqiZLE7Je3vZJHxspZdFHfnBEjHqU5hG1Jaj32dVoS6XLT1"),
+        ],
+    ];
+
+    #[test]
+    fn test_master_key_vectors() {
+        for (seed, xprv, fingerprint) in MASTER_VECTORS {
+            let master = ExtendedPrivKey::new_master(Network::Mainnet, &hex::decode(seed).unwrap()).unwrap();
+
+            assert_eq!(master.to_base58().as_str(), xprv);
+            assert_eq!(hex::encode(master.fingerprint()), fingerprint);
+            assert_eq!(master.depth(), 0);
+
+            let parsed = ExtendedPrivKey::from_base58(xprv).unwrap();
+            assert_eq!(parsed.private_key_bytes(), master.private_key_bytes());
+            assert_eq!(parsed.chain_code(), master.chain_code());
+        }
+    }
+
+    #[test]
+    fn test_testnet_serialization() {
+        let seed = hex::decode(MASTER_VECTORS[0].0).unwrap();
+        let tprv = "tprv8ZgxMBicQKsPeDgjzdC36fs6bMjGApWDNLR9erAXMs5skhMv36j9MV5ecvfavji5khqjWaWSFhN3YcCUUdiKH6isR4Pwy3U5y5egddBr16m";
+
+        assert_eq!(ExtendedPrivKey::new_master(Network::Testnet, &seed).unwrap().to_base58().as_str(), tprv);
+        assert_eq!(ExtendedPrivKey::from_base58(tprv).unwrap().network(), Network::Testnet);
+    }
+
+    #[test]
+    fn test_master_key_from_mnemonic() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+        let master = ExtendedPrivKey::from_mnemonic(&mnemonic, "", Network::Mainnet).unwrap();
+
+        assert_eq!(
+            master.to_base58().as_str(),
+            "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu"
+        );
+        assert_eq!(master.fingerprint(), [0x73, 0xc5, 0xda, 0x0a]);
+    }
+
+    #[test]
+    fn test_invalid_seed_length() {
+        assert_eq!(ExtendedPrivKey::new_master(Network::Mainnet, &[0; 15]).err(), Some(Bip32Error::BadSeedLength(15)));
+        assert_eq!(ExtendedPrivKey::new_master(Network::Mainnet, &[0; 65]).err(), Some(Bip32Error::BadSeedLength(65)));
+    }
+
+    #[test]
+    fn test_vector_5_invalid_private_keys() {
+        let invalid = [
+            // pubkey version / prvkey mismatch
+            ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6LBpB85b3D2yc8sfvZU521AAwdZafEz7mnzBBsz4wKY5fTtTQBm", Bip32Error::UnknownVersion([0x04, 0x88, 0xb2, 0x1e])),
+            // invalid prvkey prefix 04
+            ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGpWnsj83BHtEy5Zt8CcDr1UiRXuWCmTQLxEK9vbz5gPstX92JQ", Bip32Error::InvalidPrivateKey),
+            // invalid prvkey prefix 01
+            ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fEQ3Qen6J", Bip32Error::InvalidPrivateKey),
+            // zero depth with non-zero parent fingerprint
+            ("xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNTuLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv", Bip32Error::InvalidMasterFields),
+            // zero depth with non-zero index
+            ("xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF42acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN", Bip32Error::InvalidMasterFields),
+            // unknown extended key version
+            ("DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHGMQzT7ayAmfo4z3gY5KfbrZWZ6St24UVf2Qgo6oujFktLHdHY4", Bip32Error::UnknownVersion([0x01, 0x01, 0x01, 0x01])),
+            // private key 0 not in 1..n-1
+            ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzF93Y5wvzdUayhgkkFoicQZcP3y52uPPxFnfoLZB21Teqt1VvEHx", Bip32Error::InvalidPrivateKey),
+            // private key n not in 1..n-1
+            ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD5SDKr24z3aiUvKr9bJpdrcLg1y3G", Bip32Error::InvalidPrivateKey),
+            // invalid checksum
+            ("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHL", Bip32Error::InvalidChecksum),
+        ];
+
+        for (xprv, error) in invalid {
+            assert_eq!(ExtendedPrivKey::from_base58(xprv).err(), Some(error), "{}", xprv);
+        }
+
+        assert_eq!(ExtendedPrivKey::from_base58("xprv0OIl").err(), Some(Bip32Error::InvalidBase58));
+    }
+
+    #[test]
+    fn test_derivation_vectors() {
+        for ((seed, _, _), chain) in MASTER_VECTORS.iter().zip(CHAIN_VECTORS) {
+            let master = ExtendedPrivKey::new_master(Network::Mainnet, &hex::decode(seed).unwrap()).unwrap();
+
+            for (path, xpub, xprv) in chain {
+                let path: DerivationPath = path.parse().unwrap();
+                let key = master.derive_path(&path).unwrap();
+
+                assert_eq!(key.to_base58().as_str(), *xprv, "{}", path);
+                assert_eq!(key.extended_public_key().to_base58(), *xpub, "{}", path);
+                assert_eq!(key.depth() as usize, path.len());
+
+                let parsed = ExtendedPubKey::from_base58(xpub).unwrap();
+                assert_eq!(parsed, ExtendedPubKey::from(&key));
+            }
+        }
+    }
+
+    #[test]
+    fn test_public_derivation_matches_private_derivation() {
+        // m/0'/1 -> m/0'/1/2'/2 needs a hardened step, so the public derivation starts from m/0'/1/2'
+        let chain = CHAIN_VECTORS[0];
+        let parent = ExtendedPubKey::from_base58(chain[3].1).unwrap();
+
+        let path: DerivationPath = "m/2/1000000000".parse().unwrap();
+        assert_eq!(parent.derive_path(&path).unwrap().to_base58(), chain[5].1);
+
+        // Vector 2 starts with a non-hardened child of the master key
+        let master = ExtendedPubKey::from_base58(CHAIN_VECTORS[1][0].1).unwrap();
+        assert_eq!(master.derive_child(ChildNumber::Normal(0)).unwrap().to_base58(), CHAIN_VECTORS[1][1].1);
+
+        assert_eq!(
+            master.derive_child(ChildNumber::Hardened(0)).err(),
+            Some(Bip32Error::HardenedFromPublic(ChildNumber::Hardened(0)))
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_child_numbers_are_rejected() {
+        let master = ExtendedPrivKey::new_master(Network::Mainnet, &[1; 32]).unwrap();
+        let xpub = master.extended_public_key();
+
+        // Normal(2^31) must not be derived as the hardened child 0', Hardened(2^31 + 1) must not alias 1'
+        for child in [ChildNumber::Normal(1 << 31), ChildNumber::Hardened((1 << 31) + 1)] {
+            let index = match child {
+                ChildNumber::Normal(index) | ChildNumber::Hardened(index) => index,
+            };
+            assert_eq!(master.derive_child(child).err(), Some(Bip32Error::InvalidIndex(index)));
+            assert_eq!(xpub.derive_child(child).err(), Some(Bip32Error::InvalidIndex(index)));
+        }
+    }
+
+    #[test]
+    fn test_vector_5_invalid_public_keys() {
+        let invalid = [
+            // prvkey version / pubkey mismatch
+            ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGTQQD3dC4H2D5GBj7vWvSQaaBv5cxi9gafk7NF3pnBju6dwKvH", Bip32Error::UnknownVersion([0x04, 0x88, 0xad, 0xe4])),
+            // invalid pubkey prefix 04
+            ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Txnt3siSujt9RCVYsx4qHZGc62TG4McvMGcAUjeuwZdduYEvFn", Bip32Error::InvalidPublicKey),
+            // invalid pubkey prefix 01
+            ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6N8ZMMXctdiCjxTNq964yKkwrkBJJwpzZS4HS2fxvyYUA4q2Xe4", Bip32Error::InvalidPublicKey),
+            // zero depth with non-zero parent fingerprint
+            ("xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ", Bip32Error::InvalidMasterFields),
+            // zero depth with non-zero index
+            ("xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8", Bip32Error::InvalidMasterFields),
+            // unknown extended key version
+            ("DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHPmHJiEDXkTiJTVV9rHEBUem2mwVbbNfvT2MTcAqj3nesx8uBf9", Bip32Error::UnknownVersion([0x01, 0x01, 0x01, 0x01])),
+            // invalid pubkey 020000000000000000000000000000000000000000000000000000000000000007
+            ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Q5JXayek4PRsn35jii4veMimro1xefsM58PgBMrvdYre8QyULY", Bip32Error::InvalidPublicKey),
+        ];
+
+        for (xpub, error) in invalid {
+            assert_eq!(ExtendedPubKey::from_base58(xpub).err(), Some(error), "{}", xpub);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use super::Bip32Error;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+const HARDENED_OFFSET: u32 = 1 << 31;
+
+/// Index of a child key, hardened children can only be derived from the private key
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChildNumber {
+    /// Non-hardened index from 0 to 2^31 - 1
+    Normal(u32),
+    /// Hardened index from 0 to 2^31 - 1, stored without the 2^31 offset
+    Hardened(u32),
+}
+
+impl ChildNumber {
+    /// Resolves a raw 32-bit index, indexes from 2^31 are hardened
+    pub const fn from_index(index: u32) -> ChildNumber {
+        if index >= HARDENED_OFFSET {
+            ChildNumber::Hardened(index - HARDENED_OFFSET)
+        } else {
+            ChildNumber::Normal(index)
+        }
+    }
+
+    /// Non-hardened child, indexes from 2^31 are rejected instead of turning into hardened children
+    pub const fn normal(index: u32) -> Result<ChildNumber, Bip32Error> {
+        match index < HARDENED_OFFSET {
+            true => Ok(ChildNumber::Normal(index)),
+            false => Err(Bip32Error::InvalidIndex(index)),
+        }
+    }
+
+    /// Hardened child of an index without the 2^31 offset, indexes from 2^31 are rejected
+    pub const fn hardened(index: u32) -> Result<ChildNumber, Bip32Error> {
+        match index < HARDENED_OFFSET {
+            true => Ok(ChildNumber::Hardened(index)),
+            false => Err(Bip32Error::InvalidIndex(index)),
+        }
+    }
+
+    /// Raw 32-bit index like `to_index`, fails for variants built directly with an index of 2^31 or more
+    ///
+    /// `Normal(2^31)` would otherwise be derived as a hardened child and `Hardened(2^31)` as `Hardened(0)`.
+    pub const fn checked_index(&self) -> Result<u32, Bip32Error> {
+        match *self {
+            ChildNumber::Normal(index) | ChildNumber::Hardened(index) if index >= HARDENED_OFFSET => {
+                Err(Bip32Error::InvalidIndex(index))
+            }
+            _ => Ok(self.to_index()),
+        }
+    }
+
+    /// Raw 32-bit index as serialized in extended keys, with the 2^31 offset for hardened children
+    pub const fn to_index(&self) -> u32 {
+        match self {
+            ChildNumber::Normal(index) => *index,
+            ChildNumber::Hardened(index) => *index | HARDENED_OFFSET,
+        }
+    }
+
+    pub const fn is_hardened(&self) -> bool {
+        matches!(self, ChildNumber::Hardened(_))
+    }
+}
+
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChildNumber::Normal(index) => write!(f, "{}", index),
+            ChildNumber::Hardened(index) => write!(f, "{}'", index),
+        }
+    }
+}
+
+impl FromStr for ChildNumber {
+    type Err = Bip32Error;
+
+    /// Parses "5" or a hardened index written as "5'", "5h" or "5H"
+    fn from_str(component: &str) -> Result<ChildNumber, Bip32Error> {
+        let invalid = || Bip32Error::InvalidPath(component.to_string());
+
+        let (digits, hardened) = match component.strip_suffix(['\'', 'h', 'H']) {
+            Some(digits) => (digits, true),
+            None => (component, false),
+        };
+
+        // u32::from_str accepts a leading '+', indexes are plain digits only
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let index: u32 = digits.parse().map_err(|_| invalid())?;
+        if index >= HARDENED_OFFSET {
+            return Err(invalid());
+        }
+
+        Ok(if hardened { ChildNumber::Hardened(index) } else { ChildNumber::Normal(index) })
+    }
+}
+
+/// Path from the master key to a child key, e.g. `m/84'/0'/0'/0/5`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<ChildNumber>);
+
+impl DerivationPath {
+    /// Path of the master key itself, "m"
+    pub fn master() -> DerivationPath {
+        DerivationPath(Vec::new())
+    }
+
+    /// Returns a new path with `child` appended
+    pub fn child(&self, child: ChildNumber) -> DerivationPath {
+        let mut path = self.clone();
+        path.0.push(child);
+        path
+    }
+
+    /// Returns a new path with every child of `path` appended
+    pub fn extend(&self, path: &DerivationPath) -> DerivationPath {
+        let mut extended = self.clone();
+        extended.0.extend_from_slice(&path.0);
+        extended
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ChildNumber> {
+        self.0.iter()
+    }
+
+    /// Number of derivations, 0 for the master key
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl AsRef<[ChildNumber]> for DerivationPath {
+    fn as_ref(&self) -> &[ChildNumber] {
+        &self.0
+    }
+}
+
+impl From<Vec<ChildNumber>> for DerivationPath {
+    fn from(children: Vec<ChildNumber>) -> DerivationPath {
+        DerivationPath(children)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    /// Hardened children are printed with an apostrophe, e.g. "m/44'/0'/0'/0/1"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for child in &self.0 {
+            write!(f, "/{}", child)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Bip32Error;
+
+    /// Parses paths starting with "m", hardened children can use either "'" or "h" notation
+    fn from_str(path: &str) -> Result<DerivationPath, Bip32Error> {
+        let mut components = path.split('/');
+
+        if components.next() != Some("m") {
+            return Err(Bip32Error::InvalidPath(path.to_string()));
+        }
+
+        components.map(ChildNumber::from_str).collect::<Result<Vec<_>, _>>().map(DerivationPath)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_print_paths() {
+        let path: DerivationPath = "m/84'/0'/0'/0/5".parse().unwrap();
+
+        assert_eq!(path.as_ref(), &[
+            ChildNumber::Hardened(84),
+            ChildNumber::Hardened(0),
+            ChildNumber::Hardened(0),
+            ChildNumber::Normal(0),
+            ChildNumber::Normal(5),
+        ]);
+        assert_eq!(path.to_string(), "m/84'/0'/0'/0/5");
+
+        // h notation is printed back with apostrophes
+        assert_eq!("m/44h/60H/0h/0/1".parse::<DerivationPath>().unwrap().to_string(), "m/44'/60'/0'/0/1");
+        assert_eq!("m".parse::<DerivationPath>().unwrap(), DerivationPath::master());
+        assert_eq!(DerivationPath::master().child(ChildNumber::Hardened(1)).to_string(), "m/1'");
+    }
+
+    #[test]
+    fn test_child_number_index() {
+        assert_eq!(ChildNumber::from_index(0x8000_0002), ChildNumber::Hardened(2));
+        assert_eq!(ChildNumber::Hardened(2147483647).to_index(), u32::MAX);
+        assert_eq!(ChildNumber::Normal(7).to_index(), 7);
+
+        assert_eq!(ChildNumber::normal(5), Ok(ChildNumber::Normal(5)));
+        assert_eq!(ChildNumber::hardened(HARDENED_OFFSET - 1), Ok(ChildNumber::Hardened(HARDENED_OFFSET - 1)));
+        assert_eq!(ChildNumber::normal(HARDENED_OFFSET), Err(Bip32Error::InvalidIndex(HARDENED_OFFSET)));
+        assert_eq!(ChildNumber::hardened(u32::MAX), Err(Bip32Error::InvalidIndex(u32::MAX)));
+
+        assert_eq!(ChildNumber::Hardened(2).checked_index(), Ok(0x8000_0002));
+        assert_eq!(ChildNumber::Normal(HARDENED_OFFSET).checked_index(), Err(Bip32Error::InvalidIndex(HARDENED_OFFSET)));
+        assert_eq!(ChildNumber::Hardened(HARDENED_OFFSET).checked_index(), Err(Bip32Error::InvalidIndex(HARDENED_OFFSET)));
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        for path in ["", "/0", "m/", "m//1", "n/0", "m/0/x", "m/-1", "m/+1", "m/1''", "m/2147483648", "0/1", "m/0 /1"] {
+            assert!(path.parse::<DerivationPath>().is_err(), "{:?}", path);
+        }
+    }
+}
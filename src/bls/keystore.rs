@@ -0,0 +1,518 @@
+/*
+    EIP-2335 keystores, the JSON files written by the staking deposit CLI and imported by validator clients.
+
+    The password is normalized to NFKD without control characters, then stretched with scrypt or PBKDF2 into a
+    32-byte decryption key. The secret key is encrypted with AES-128-CTR under the first 16 bytes, and the checksum
+    is SHA-256 of the last 16 bytes followed by the ciphertext, which detects a wrong password before decrypting.
+    Keystores are read back with the `alloc` build of serde_json, so the parser works without std as well.
+*/
+
+use super::{BlsError, SecretKey};
+use crate::utils;
+use aes::cipher::{KeyIvInit, StreamCipher};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use rand::rngs::OsRng;
+use rand_core::{CryptoRng, RngCore};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// Password-based key derivation function of a keystore
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Kdf {
+    /// scrypt with cost `n` (a power of 2), block size `r` and parallelization `p`
+    Scrypt { n: u32, r: u32, p: u32 },
+    /// PBKDF2-HMAC-SHA256 with `c` iterations
+    Pbkdf2 { c: u32 },
+}
+
+impl Kdf {
+    /// scrypt parameters of the staking deposit CLI and of the EIP-2335 test vectors
+    pub const SCRYPT: Kdf = Kdf::Scrypt { n: 262144, r: 8, p: 1 };
+    /// PBKDF2 parameters of the EIP-2335 test vectors
+    pub const PBKDF2: Kdf = Kdf::Pbkdf2 { c: 262144 };
+
+    fn derive_key(&self, password: &str, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>, BlsError> {
+        let password = process_password(password);
+        let mut key = Zeroizing::new([0u8; 32]);
+
+        match *self {
+            Kdf::Scrypt { n, r, p } => {
+                if n < 2 || !n.is_power_of_two() {
+                    return Err(BlsError::InvalidKdfParameters);
+                }
+                let params = scrypt::Params::new(n.trailing_zeros() as u8, r, p, key.len())
+                    .map_err(|_| BlsError::InvalidKdfParameters)?;
+                scrypt::scrypt(password.as_bytes(), salt, &params, &mut *key).map_err(|_| BlsError::InvalidKdfParameters)?;
+            }
+            Kdf::Pbkdf2 { c } => {
+                if c == 0 {
+                    return Err(BlsError::InvalidKdfParameters);
+                }
+                pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, c, &mut *key);
+            }
+        }
+        Ok(key)
+    }
+
+    fn to_json(self, salt: &[u8; 32]) -> String {
+        match self {
+            Kdf::Scrypt { n, r, p } => format!(
+                r#"{{"function":"scrypt","params":{{"dklen":32,"n":{},"r":{},"p":{},"salt":"{}"}},"message":""}}"#,
+                n, r, p, hex::encode(salt)
+            ),
+            Kdf::Pbkdf2 { c } => format!(
+                r#"{{"function":"pbkdf2","params":{{"dklen":32,"c":{},"prf":"hmac-sha256","salt":"{}"}},"message":""}}"#,
+                c, hex::encode(salt)
+            ),
+        }
+    }
+}
+
+/// Encrypted secret key in the EIP-2335 format (version 4)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keystore {
+    kdf: Kdf,
+    salt: [u8; 32],
+    iv: [u8; 16],
+    checksum: [u8; 32],
+    ciphertext: [u8; 32],
+    pubkey: [u8; 48],
+    path: String,
+    uuid: [u8; 16],
+    description: String,
+}
+
+impl Keystore {
+    /// Encrypts `secret_key` with a random salt, IV and UUID drawn from the given cryptographically secure RNG
+    ///
+    /// `path` is the EIP-2334 path of the key, e.g. "m/12381/3600/0/0/0", or "" for a key that was not derived.
+    pub fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        secret_key: &SecretKey,
+        path: &str,
+        password: &str,
+        kdf: Kdf,
+    ) -> Result<Keystore, BlsError> {
+        let mut salt = [0u8; 32];
+        let mut iv = [0u8; 16];
+        let mut uuid = [0u8; 16];
+        for bytes in [&mut salt[..], &mut iv, &mut uuid] {
+            rng.try_fill_bytes(bytes).map_err(BlsError::GeneratorError)?;
+        }
+
+        // Random UUID: version 4, variant 1
+        uuid[6] = uuid[6] & 0x0f | 0x40;
+        uuid[8] = uuid[8] & 0x3f | 0x80;
+
+        Self::encrypt_with(secret_key, path, password, kdf, salt, iv, uuid)
+    }
+
+    /// Encrypts `secret_key` with a salt, IV and UUID from the OS random number generator
+    #[cfg(feature = "std")]
+    pub fn encrypt(secret_key: &SecretKey, path: &str, password: &str, kdf: Kdf) -> Result<Keystore, BlsError> {
+        Self::encrypt_with_rng(&mut OsRng, secret_key, path, password, kdf)
+    }
+
+    /// Parses keystore JSON of version 4, e.g. a keystore-m_12381_3600_0_0_0-*.json file of the staking deposit CLI
+    ///
+    /// Only 32-byte salts and derived keys are supported, as written by the deposit CLI and in the EIP-2335 test
+    /// vectors. The password isn't checked here, `decrypt` does that.
+    pub fn from_json(json: &str) -> Result<Keystore, BlsError> {
+        let json: Value = serde_json::from_str(json).map_err(|_| BlsError::InvalidKeystore)?;
+        let crypto = &json["crypto"];
+        let kdf_params = &crypto["kdf"]["params"];
+
+        if json["version"] != 4
+            || kdf_params["dklen"] != 32
+            || crypto["checksum"]["function"] != "sha256"
+            || crypto["cipher"]["function"] != "aes-128-ctr"
+        {
+            return Err(BlsError::InvalidKeystore);
+        }
+
+        let kdf = match crypto["kdf"]["function"].as_str() {
+            Some("scrypt") => Kdf::Scrypt {
+                n: json_u32(&kdf_params["n"])?,
+                r: json_u32(&kdf_params["r"])?,
+                p: json_u32(&kdf_params["p"])?,
+            },
+            Some("pbkdf2") if kdf_params["prf"] == "hmac-sha256" => Kdf::Pbkdf2 { c: json_u32(&kdf_params["c"])? },
+            _ => return Err(BlsError::InvalidKeystore),
+        };
+
+        // Hyphenated UUID, 8-4-4-4-12 hex digits
+        let uuid = json_str(&json["uuid"])?;
+        let hyphens = uuid.char_indices().filter(|(_, character)| *character == '-').map(|(position, _)| position);
+        if !hyphens.eq([8, 13, 18, 23]) {
+            return Err(BlsError::InvalidKeystore);
+        }
+
+        Ok(Keystore {
+            kdf,
+            salt: json_hex(&kdf_params["salt"])?,
+            iv: json_hex(&crypto["cipher"]["params"]["iv"])?,
+            checksum: json_hex(&crypto["checksum"]["message"])?,
+            ciphertext: json_hex(&crypto["cipher"]["message"])?,
+            pubkey: json_hex(&json["pubkey"])?,
+            path: json_str(&json["path"])?.into(),
+            uuid: hex_array(&uuid.replace('-', ""))?,
+            // The description is optional in EIP-2335
+            description: json["description"].as_str().unwrap_or_default().into(),
+        })
+    }
+
+    /// Sets the free-form description shown by validator clients
+    pub fn with_description(mut self, description: &str) -> Keystore {
+        self.description = description.into();
+        self
+    }
+
+    /// Decrypts the secret key, the password is checked against the checksum first
+    pub fn decrypt(&self, password: &str) -> Result<SecretKey, BlsError> {
+        let key = self.kdf.derive_key(password, &self.salt)?;
+        if checksum(&key, &self.ciphertext) != self.checksum {
+            return Err(BlsError::InvalidPassword);
+        }
+
+        let mut secret = Zeroizing::new(self.ciphertext);
+        Aes128Ctr::new(key[..16].into(), (&self.iv).into()).apply_keystream(&mut *secret);
+        SecretKey::from_bytes(&secret)
+    }
+
+    /// Compressed public key of the encrypted secret key
+    pub fn pubkey(&self) -> &[u8; 48] {
+        &self.pubkey
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// UUID in its hyphenated form, e.g. 1d85ae20-35c5-4611-98e8-aa14a633906f
+    pub fn uuid(&self) -> String {
+        let hex = hex::encode(self.uuid);
+        format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn kdf(&self) -> Kdf {
+        self.kdf
+    }
+
+    /// Keystore JSON, in the layout of the staking deposit CLI (keystore-m_12381_3600_0_0_0-*.json)
+    pub fn to_json(&self) -> String {
+        format!(
+            concat!(
+                r#"{{"crypto":{{"kdf":{},"#,
+                r#""checksum":{{"function":"sha256","params":{{}},"message":"{}"}},"#,
+                r#""cipher":{{"function":"aes-128-ctr","params":{{"iv":"{}"}},"message":"{}"}}}},"#,
+                r#""description":"{}","pubkey":"{}","path":"{}","uuid":"{}","version":4}}"#,
+            ),
+            self.kdf.to_json(&self.salt),
+            hex::encode(self.checksum),
+            hex::encode(self.iv),
+            hex::encode(self.ciphertext),
+            escape_json(&self.description),
+            hex::encode(self.pubkey),
+            escape_json(&self.path),
+            self.uuid(),
+        )
+    }
+
+    fn encrypt_with(
+        secret_key: &SecretKey,
+        path: &str,
+        password: &str,
+        kdf: Kdf,
+        salt: [u8; 32],
+        iv: [u8; 16],
+        uuid: [u8; 16],
+    ) -> Result<Keystore, BlsError> {
+        let key = kdf.derive_key(password, &salt)?;
+
+        let mut ciphertext = *secret_key.to_bytes();
+        Aes128Ctr::new(key[..16].into(), (&iv).into()).apply_keystream(&mut ciphertext);
+
+        Ok(Keystore {
+            kdf,
+            salt,
+            iv,
+            checksum: checksum(&key, &ciphertext),
+            ciphertext,
+            pubkey: secret_key.public_key(),
+            path: path.into(),
+            uuid,
+            description: String::new(),
+        })
+    }
+}
+
+/// NFKD with the C0, DEL and C1 control codes removed, as required by EIP-2335
+fn process_password(password: &str) -> Zeroizing<String> {
+    let normalized = utils::normalize(password);
+
+    let mut processed = Zeroizing::new(String::with_capacity(normalized.len()));
+    processed.extend(normalized.chars().filter(|character| !character.is_control()));
+    processed
+}
+
+fn checksum(key: &[u8; 32], ciphertext: &[u8; 32]) -> [u8; 32] {
+    Sha256::new().chain_update(&key[16..]).chain_update(ciphertext).finalize().into()
+}
+
+fn json_str(value: &Value) -> Result<&str, BlsError> {
+    value.as_str().ok_or(BlsError::InvalidKeystore)
+}
+
+fn json_u32(value: &Value) -> Result<u32, BlsError> {
+    value.as_u64().and_then(|number| u32::try_from(number).ok()).ok_or(BlsError::InvalidKeystore)
+}
+
+fn json_hex<const N: usize>(value: &Value) -> Result<[u8; N], BlsError> {
+    hex_array(json_str(value)?)
+}
+
+fn hex_array<const N: usize>(text: &str) -> Result<[u8; N], BlsError> {
+    let mut bytes = [0u8; N];
+    hex::decode_to_slice(text, &mut bytes).map_err(|_| BlsError::InvalidKeystore)?;
+    Ok(bytes)
+}
+
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            character if character.is_control() => {
+                write!(escaped, "\\u{:04x}", character as u32).expect("Writing to a String never fails")
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Secret key, password, salt and IV of the EIP-2335 test vectors, the password is "testpassword🔑" in NFKD
+    const SECRET: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+    const PASSWORD: &str = "𝔱𝔢𝔰𝔱𝔭𝔞𝔰𝔰𝔴𝔬𝔯𝔡🔑";
+    const SALT: &str = "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3";
+    const IV: &str = "264daa3f303d7259501c93d997d84fe6";
+    const PUBKEY: &str = "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07";
+    const PATH: &str = "m/12381/60/3141592653/589793238";
+    const UUID: &str = "1d85ae20-35c5-4611-98e8-aa14a633906f";
+
+    fn vector_keystore(kdf: Kdf) -> Keystore {
+        let secret_key = SecretKey::from_bytes(&hex::decode(SECRET).unwrap().try_into().unwrap()).unwrap();
+        let salt = hex::decode(SALT).unwrap().try_into().unwrap();
+        let iv = hex::decode(IV).unwrap().try_into().unwrap();
+        let uuid = hex::decode(UUID.replace('-', "")).unwrap().try_into().unwrap();
+
+        Keystore::encrypt_with(&secret_key, PATH, PASSWORD, kdf, salt, iv, uuid).unwrap()
+    }
+
+    #[test]
+    fn test_eip2335_scrypt_vector() {
+        let keystore = vector_keystore(Kdf::SCRYPT)
+            .with_description("This is a test keystore that uses scrypt to secure the secret.");
+        let json: serde_json::Value = serde_json::from_str(&keystore.to_json()).unwrap();
+
+        assert_eq!(json["crypto"]["kdf"]["params"]["n"], 262144);
+        assert_eq!(json["crypto"]["kdf"]["params"]["salt"], SALT);
+        assert_eq!(json["crypto"]["checksum"]["message"], "d2217fe5f3e9a1e34581ef8a78f7c9928e436d36dacc5e846690a5581e8ea484");
+        assert_eq!(json["crypto"]["cipher"]["params"]["iv"], IV);
+        assert_eq!(json["crypto"]["cipher"]["message"], "06ae90d55fe0a6e9c5c3bc5b170827b2e5cce3929ed3f116c2811e6366dfe20f");
+        assert_eq!(json["description"], "This is a test keystore that uses scrypt to secure the secret.");
+        assert_eq!(json["pubkey"], PUBKEY);
+        assert_eq!(json["path"], PATH);
+        assert_eq!(json["uuid"], UUID);
+        assert_eq!(json["version"], 4);
+    }
+
+    #[test]
+    fn test_eip2335_pbkdf2_vector() {
+        let keystore = vector_keystore(Kdf::PBKDF2);
+        let json: serde_json::Value = serde_json::from_str(&keystore.to_json()).unwrap();
+
+        assert_eq!(json["crypto"]["kdf"]["function"], "pbkdf2");
+        assert_eq!(json["crypto"]["kdf"]["params"]["c"], 262144);
+        assert_eq!(json["crypto"]["kdf"]["params"]["prf"], "hmac-sha256");
+        assert_eq!(json["crypto"]["checksum"]["message"], "8a9f5d9912ed7e75ea794bc5a89bca5f193721d30868ade6f73043c6ea6febf1");
+        assert_eq!(json["crypto"]["cipher"]["message"], "cee03fde2af33149775b7223e7845e4fb2c8ae1792e5f99fe9ecf474cc8c16ad");
+
+        assert_eq!(hex::encode(*keystore.decrypt(PASSWORD).unwrap().to_bytes()), SECRET);
+    }
+
+    // The keystores of the EIP-2335 test vectors, as published
+    const SCRYPT_JSON: &str = r#"{
+        "crypto": {
+            "kdf": {
+                "function": "scrypt",
+                "params": {
+                    "dklen": 32,
+                    "n": 262144,
+                    "p": 1,
+                    "r": 8,
+                    "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                },
+                "message": ""
+            },
+            "checksum": {
+                "function": "sha256",
+                "params": {},
+                "message": "d2217fe5f3e9a1e34581ef8a78f7c9928e436d36dacc5e846690a5581e8ea484"
+            },
+            "cipher": {
+                "function": "aes-128-ctr",
+                "params": {
+                    "iv": "264daa3f303d7259501c93d997d84fe6"
+                },
+                "message": "06ae90d55fe0a6e9c5c3bc5b170827b2e5cce3929ed3f116c2811e6366dfe20f"
+            }
+        },
+        "description": "This is a test keystore that uses scrypt to secure the secret.",
+        "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+        "path": "m/12381/60/3141592653/589793238",
+        "uuid": "1d85ae20-35c5-4611-98e8-aa14a633906f",
+        "version": 4
+    }"#;
+    const PBKDF2_JSON: &str = r#"{
+        "crypto": {
+            "kdf": {
+                "function": "pbkdf2",
+                "params": {
+                    "dklen": 32,
+                    "c": 262144,
+                    "prf": "hmac-sha256",
+                    "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                },
+                "message": ""
+            },
+            "checksum": {
+                "function": "sha256",
+                "params": {},
+                "message": "8a9f5d9912ed7e75ea794bc5a89bca5f193721d30868ade6f73043c6ea6febf1"
+            },
+            "cipher": {
+                "function": "aes-128-ctr",
+                "params": {
+                    "iv": "264daa3f303d7259501c93d997d84fe6"
+                },
+                "message": "cee03fde2af33149775b7223e7845e4fb2c8ae1792e5f99fe9ecf474cc8c16ad"
+            }
+        },
+        "description": "This is a test keystore that uses PBKDF2 to secure the secret.",
+        "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+        "path": "m/12381/60/0/0",
+        "uuid": "64625def-3331-4eea-ab6f-782f3ed16a83",
+        "version": 4
+    }"#;
+
+    #[test]
+    fn test_decrypt_eip2335_json_vectors() {
+        let scrypt = Keystore::from_json(SCRYPT_JSON).unwrap();
+        assert_eq!(scrypt.kdf(), Kdf::SCRYPT);
+        assert_eq!(scrypt.path(), PATH);
+        assert_eq!(scrypt.uuid(), UUID);
+        assert_eq!(hex::encode(scrypt.pubkey()), PUBKEY);
+        assert_eq!(hex::encode(*scrypt.decrypt(PASSWORD).unwrap().to_bytes()), SECRET);
+        assert_eq!(Keystore::from_json(&scrypt.to_json()).unwrap(), scrypt);
+
+        let pbkdf2 = Keystore::from_json(PBKDF2_JSON).unwrap();
+        assert_eq!(pbkdf2.kdf(), Kdf::PBKDF2);
+        assert_eq!(pbkdf2.description(), "This is a test keystore that uses PBKDF2 to secure the secret.");
+        assert_eq!(hex::encode(*pbkdf2.decrypt(PASSWORD).unwrap().to_bytes()), SECRET);
+        assert!(matches!(pbkdf2.decrypt("testpassword"), Err(BlsError::InvalidPassword)));
+    }
+
+    #[test]
+    fn test_invalid_keystore_json() {
+        let invalid = [
+            SCRYPT_JSON.replace(r#""version": 4"#, r#""version": 3"#),
+            SCRYPT_JSON.replace(r#""dklen": 32"#, r#""dklen": 64"#),
+            SCRYPT_JSON.replace(r#""n": 262144"#, r#""n": 4294967296"#),
+            SCRYPT_JSON.replace(r#""function": "scrypt""#, r#""function": "argon2""#),
+            SCRYPT_JSON.replace("aes-128-ctr", "aes-128-cbc"),
+            SCRYPT_JSON.replace("264daa3f303d7259501c93d997d84fe6", "264daa3f303d7259501c93d997d84f"),
+            SCRYPT_JSON.replace("1d85ae20-35c5", "1d85ae2035c5-"),
+            SCRYPT_JSON.replace(r#""path": "m/12381/60/3141592653/589793238","#, ""),
+            PBKDF2_JSON.replace("hmac-sha256", "hmac-sha512"),
+            String::from("{}"),
+            String::from("not json"),
+        ];
+
+        for json in invalid {
+            assert!(matches!(Keystore::from_json(&json), Err(BlsError::InvalidKeystore)), "{}", json);
+        }
+
+        // The description is optional
+        let without_description = SCRYPT_JSON.replace(
+            r#""description": "This is a test keystore that uses scrypt to secure the secret.","#,
+            "",
+        );
+        assert_eq!(Keystore::from_json(&without_description).unwrap().description(), "");
+    }
+
+    #[test]
+    fn test_random_keystore() {
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        let mut rng = ChaCha20Rng::from_seed([7u8; 32]);
+        let secret_key = SecretKey::derive_master(&[1; 32]).unwrap();
+        let kdf = Kdf::Scrypt { n: 16, r: 8, p: 1 };
+
+        let keystore = Keystore::encrypt_with_rng(&mut rng, &secret_key, "m/12381/3600/0/0/0", "password", kdf)
+            .unwrap()
+            .with_description("quote \" and backslash \\");
+        let uuid = keystore.uuid();
+        assert_eq!(&uuid[14..15], "4");
+        assert!("89ab".contains(&uuid[19..20]));
+
+        let json: serde_json::Value = serde_json::from_str(&keystore.to_json()).unwrap();
+        assert_eq!(json["description"], "quote \" and backslash \\");
+        assert_eq!(keystore.decrypt("password").unwrap().to_bytes(), secret_key.to_bytes());
+        assert!(matches!(keystore.decrypt("Password"), Err(BlsError::InvalidPassword)));
+        // Passwords are compared in NFKD without control codes
+        assert!(keystore.decrypt("pass\u{7f}word\n").is_ok());
+        assert!(keystore.decrypt("ｐａｓｓｗｏｒｄ").is_ok());
+
+        let invalid = Keystore::encrypt_with_rng(&mut rng, &secret_key, "", "password", Kdf::Scrypt { n: 15, r: 8, p: 1 });
+        assert!(matches!(invalid, Err(BlsError::InvalidKdfParameters)));
+    }
+
+    #[test]
+    fn test_encrypt_with_failing_rng() {
+        struct FailingRng;
+
+        impl RngCore for FailingRng {
+            fn next_u32(&mut self) -> u32 { unimplemented!() }
+            fn next_u64(&mut self) -> u64 { unimplemented!() }
+            fn fill_bytes(&mut self, _dest: &mut [u8]) { unimplemented!() }
+            fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                Err(rand_core::Error::from(core::num::NonZeroU32::new(rand_core::Error::CUSTOM_START).unwrap()))
+            }
+        }
+
+        impl CryptoRng for FailingRng {}
+
+        let secret_key = SecretKey::derive_master(&[1; 32]).unwrap();
+        let result = Keystore::encrypt_with_rng(&mut FailingRng, &secret_key, "", "password", Kdf::PBKDF2);
+
+        assert!(matches!(result, Err(BlsError::GeneratorError(_))));
+    }
+}
@@ -0,0 +1,365 @@
+/*
+    Ethereum validator keys: BLS12-381 key tree of EIP-2333 with the paths of EIP-2334.
+
+    Keys are derived with HKDF-SHA256 instead of HMAC-SHA512. A child key is hashed from a Lamport public key
+    built from the parent secret key (HKDF_mod_r of its compressed form), so, unlike BIP-32, the public key of a
+    parent gives no information about its children and every derivation is "hardened".
+    EIP-2334 places the withdrawal key of validator i at m/12381/3600/i/0 and its signing key at m/12381/3600/i/0/0.
+*/
+
+pub mod keystore;
+
+pub use keystore::{Kdf, Keystore};
+
+use crate::bip32::{ChildNumber, DerivationPath};
+use crate::Mnemonic;
+use alloc::string::ToString;
+use bls12_381::{G1Affine, Scalar};
+use hkdf::{Hkdf, HkdfExtract};
+#[cfg(feature = "std")]
+use rand::rngs::OsRng;
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+const KEYGEN_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+const LAMPORT_CHUNKS: usize = 255;
+
+/// Purpose of EIP-2334 paths, the name of the curve
+pub const PURPOSE: u32 = 12381;
+/// Coin type of Ethereum in EIP-2334 paths
+pub const COIN_TYPE: u32 = 3600;
+
+#[derive(Debug)]
+pub enum BlsError {
+    /// Seed is shorter than the 32 bytes required by EIP-2333
+    SeedTooShort(usize),
+    /// Secret key is 0 or not below r, the order of BLS12-381
+    InvalidSecretKey,
+    /// Scrypt or PBKDF2 parameters are out of range, or scrypt's n is not a power of 2
+    InvalidKdfParameters,
+    /// Keystore checksum doesn't match, the password is wrong or the keystore was modified
+    InvalidPassword,
+    /// Validator index is 2^31 or more, it can't be written in an EIP-2334 path
+    InvalidIndex(u32),
+    /// Random number generator failed to provide the salt, IV or UUID of a keystore
+    GeneratorError(rand_core::Error),
+    /// Keystore JSON is malformed, or isn't a version 4 keystore with the parameters supported by `Keystore`
+    InvalidKeystore,
+}
+
+impl core::fmt::Display for BlsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            BlsError::SeedTooShort(bytes) => {
+                write!(f, "Invalid seed length: expected at least 32 bytes, but got {} bytes.", bytes)
+            }
+            BlsError::InvalidSecretKey => write!(f, "Invalid secret key for BLS12-381."),
+            BlsError::InvalidKdfParameters => write!(f, "Invalid key derivation function parameters."),
+            BlsError::InvalidPassword => write!(f, "Invalid keystore password: checksum doesn't match."),
+            BlsError::InvalidIndex(index) => write!(f, "Validator index {} is above the maximum of 2^31 - 1.", index),
+            BlsError::GeneratorError(e) => write!(f, "Error when generating keystore randomness: {}.", e),
+            BlsError::InvalidKeystore => write!(f, "Invalid or unsupported EIP-2335 keystore JSON."),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BlsError::GeneratorError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// BLS12-381 secret key, stored as its 32-byte big-endian encoding and wiped when dropped
+pub struct SecretKey {
+    bytes: Zeroizing<[u8; 32]>,
+}
+
+impl SecretKey {
+    /// Derives the master key (derive_master_SK) from a seed of at least 32 bytes
+    pub fn derive_master(seed: &[u8]) -> Result<SecretKey, BlsError> {
+        if seed.len() < 32 {
+            return Err(BlsError::SeedTooShort(seed.len()));
+        }
+        Ok(hkdf_mod_r(seed))
+    }
+
+    /// Derives the master key from the seed of a mnemonic and its passphrase, as the staking deposit CLI does
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Result<SecretKey, BlsError> {
+        Self::derive_master(&*mnemonic.to_seed(passphrase))
+    }
+
+    /// Parses a 32-byte big-endian secret key
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<SecretKey, BlsError> {
+        let mut little_endian = Zeroizing::new(*bytes);
+        little_endian.reverse();
+
+        match Option::<Scalar>::from(Scalar::from_bytes(&little_endian)) {
+            Some(scalar) if scalar != Scalar::zero() => Ok(SecretKey { bytes: Zeroizing::new(*bytes) }),
+            _ => Err(BlsError::InvalidSecretKey),
+        }
+    }
+
+    /// Derives a child key (derive_child_SK), every 32-bit index is allowed
+    pub fn derive_child(&self, index: u32) -> SecretKey {
+        hkdf_mod_r(&parent_sk_to_lamport_pk(&self.bytes, index))
+    }
+
+    /// Derives the key at `path`, relative to this key, hardened children use their raw index with the 2^31 offset
+    pub fn derive_path(&self, path: &DerivationPath) -> SecretKey {
+        path.iter().fold(self.clone_key(), |key, child| key.derive_child(child.to_index()))
+    }
+
+    /// 32-byte big-endian secret key, wiped when dropped
+    pub fn to_bytes(&self) -> Zeroizing<[u8; 32]> {
+        self.bytes.clone()
+    }
+
+    /// 48-byte compressed G1 public key, the validator pubkey of deposits
+    pub fn public_key(&self) -> [u8; 48] {
+        let mut little_endian = Zeroizing::new(*self.bytes);
+        little_endian.reverse();
+        let scalar = Option::<Scalar>::from(Scalar::from_bytes(&little_endian)).expect("Secret key is below r");
+
+        G1Affine::from(G1Affine::generator() * scalar).to_compressed()
+    }
+
+    fn clone_key(&self) -> SecretKey {
+        SecretKey { bytes: self.bytes.clone() }
+    }
+}
+
+/// Withdrawal and signing keys of a validator, at the EIP-2334 paths m/12381/3600/index/0 and m/12381/3600/index/0/0
+pub struct Validator {
+    index: u32,
+    withdrawal_key: SecretKey,
+    signing_key: SecretKey,
+}
+
+impl Validator {
+    /// Derives the keys of validator `index` from the master key, the index must be below 2^31
+    pub fn new(master: &SecretKey, index: u32) -> Result<Validator, BlsError> {
+        let withdrawal_key = master.derive_path(&Self::withdrawal_key_path(index)?);
+        let signing_key = withdrawal_key.derive_child(0);
+
+        Ok(Validator { index, withdrawal_key, signing_key })
+    }
+
+    /// Derives the keys of validator `index` from the seed of a mnemonic and its passphrase
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str, index: u32) -> Result<Validator, BlsError> {
+        Self::new(&SecretKey::from_mnemonic(mnemonic, passphrase)?, index)
+    }
+
+    /// Path of the withdrawal key, m/12381/3600/index/0
+    ///
+    /// EIP-2334 paths have no hardened levels, an index of 2^31 or more is an `InvalidIndex` error.
+    pub fn withdrawal_key_path(index: u32) -> Result<DerivationPath, BlsError> {
+        Ok(DerivationPath::from(alloc::vec![
+            ChildNumber::Normal(PURPOSE),
+            ChildNumber::Normal(COIN_TYPE),
+            ChildNumber::normal(index).map_err(|_| BlsError::InvalidIndex(index))?,
+            ChildNumber::Normal(0),
+        ]))
+    }
+
+    /// Path of the signing key, m/12381/3600/index/0/0
+    pub fn signing_key_path(index: u32) -> Result<DerivationPath, BlsError> {
+        Ok(Self::withdrawal_key_path(index)?.child(ChildNumber::Normal(0)))
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Key used to withdraw the stake (BLS withdrawal credentials)
+    pub fn withdrawal_key(&self) -> &SecretKey {
+        &self.withdrawal_key
+    }
+
+    /// Key used by the validator client to sign attestations and blocks
+    pub fn signing_key(&self) -> &SecretKey {
+        &self.signing_key
+    }
+
+    /// EIP-2335 keystore of the signing key, with a salt, IV and UUID drawn from the given RNG
+    pub fn signing_keystore_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        password: &str,
+        kdf: Kdf,
+    ) -> Result<Keystore, BlsError> {
+        let path = Self::signing_key_path(self.index)?.to_string();
+        Keystore::encrypt_with_rng(rng, &self.signing_key, &path, password, kdf)
+    }
+
+    /// EIP-2335 keystore of the signing key, the file the staking deposit CLI writes for validator clients
+    #[cfg(feature = "std")]
+    pub fn signing_keystore(&self, password: &str, kdf: Kdf) -> Result<Keystore, BlsError> {
+        self.signing_keystore_with_rng(&mut OsRng, password, kdf)
+    }
+}
+
+/// HKDF_mod_r of EIP-2333: hashes the key material to a non-zero scalar of BLS12-381
+fn hkdf_mod_r(ikm: &[u8]) -> SecretKey {
+    let mut salt: [u8; 32] = Sha256::digest(KEYGEN_SALT).into();
+
+    loop {
+        let mut extract = HkdfExtract::<Sha256>::new(Some(&salt));
+        extract.input_ikm(ikm);
+        extract.input_ikm(&[0]);
+        let (_, hkdf) = extract.finalize();
+
+        // key_info is empty, followed by the output length L = 48 as 2 bytes
+        let mut okm = Zeroizing::new([0u8; 48]);
+        hkdf.expand(&48u16.to_be_bytes(), &mut *okm).expect("48 bytes is a valid HKDF-SHA256 length");
+
+        // OS2IP(OKM) mod r, the big-endian output is reversed into the little-endian wide encoding of Scalar
+        let mut wide = Zeroizing::new([0u8; 64]);
+        for (wide, byte) in wide.iter_mut().zip(okm.iter().rev()) {
+            *wide = *byte;
+        }
+        let scalar = Scalar::from_bytes_wide(&wide);
+
+        if scalar != Scalar::zero() {
+            let mut bytes = Zeroizing::new(scalar.to_bytes());
+            bytes.reverse();
+            return SecretKey { bytes };
+        }
+        salt = Sha256::digest(salt).into();
+    }
+}
+
+/// parent_SK_to_lamport_PK of EIP-2333: compressed Lamport public key of the parent key, for child `index`
+fn parent_sk_to_lamport_pk(parent: &[u8; 32], index: u32) -> [u8; 32] {
+    let salt = index.to_be_bytes();
+    let mut flipped = Zeroizing::new(*parent);
+    flipped.iter_mut().for_each(|byte| *byte = !*byte);
+
+    // lamport_0 comes from the key, lamport_1 from its flipped bits, each of 255 chunks of 32 bytes
+    let mut compressed = Sha256::new();
+    for ikm in [parent, &*flipped] {
+        let mut lamport = Zeroizing::new([0u8; 32 * LAMPORT_CHUNKS]);
+        Hkdf::<Sha256>::new(Some(&salt), ikm)
+            .expand(&[], &mut *lamport)
+            .expect("8160 bytes is the maximum HKDF-SHA256 length");
+
+        for chunk in lamport.chunks(32) {
+            compressed.update(Sha256::digest(chunk));
+        }
+    }
+    compressed.finalize().into()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    // (seed, master secret key, child index, child secret key) of the test cases of EIP-2333, keys in decimal
+    const VECTORS: [(&str, &str, u32, &str); 4] = [
+        (
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            "6083874454709270928345386274498605044986640685124978867557563392430687146096",
+            0,
+            "20397789859736650942317412262472558107875392172444076792671091975210932703118",
+        ),
+        (
+            "3141592653589793238462643383279502884197169399375105820974944592",
+            "29757020647961307431480504535336562678282505419141012933316116377660817309383",
+            3141592653,
+            "25457201688850691947727629385191704516744796114925897962676248250929345014287",
+        ),
+        (
+            "0099ff991111002299dd7744ee3355bbdd8844115566cc55663355668888cc00",
+            "27580842291869792442942448775674722299803720648445448686099262467207037398656",
+            4294967295,
+            "29358610794459428860402234341874281240803786294062035874021252734817515685787",
+        ),
+        (
+            "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+            "19022158461524446591288038168518313374041767046816487870552872741050760015818",
+            42,
+            "31372231650479070279774297061823572166496564838472787488249775572789064611981",
+        ),
+    ];
+
+    fn decimal(key: &SecretKey) -> String {
+        // Long division of the big-endian bytes by 10, enough for test output
+        let mut digits = Vec::new();
+        let mut number = key.to_bytes().to_vec();
+
+        while number.iter().any(|byte| *byte != 0) {
+            let mut remainder = 0u32;
+            for byte in number.iter_mut() {
+                let value = remainder << 8 | *byte as u32;
+                *byte = (value / 10) as u8;
+                remainder = value % 10;
+            }
+            digits.push(char::from(b'0' + remainder as u8));
+        }
+        digits.iter().rev().collect()
+    }
+
+    #[test]
+    fn test_eip2333_vectors() {
+        for (seed, master_sk, index, child_sk) in VECTORS {
+            let master = SecretKey::derive_master(&hex::decode(seed).unwrap()).unwrap();
+
+            assert_eq!(decimal(&master), master_sk);
+            assert_eq!(decimal(&master.derive_child(index)), child_sk);
+        }
+    }
+
+    #[test]
+    fn test_eip2334_validator_keys() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+        let validator = Validator::from_mnemonic(&mnemonic, "", 0).unwrap();
+
+        assert_eq!(Validator::signing_key_path(0).unwrap().to_string(), "m/12381/3600/0/0/0");
+        assert_eq!(
+            hex::encode(*validator.signing_key().to_bytes()),
+            "3ec45abb2792f1f287ab1434acfde9d7aac879eb74c45cf7b59d25f15ba7a650"
+        );
+        assert_eq!(
+            hex::encode(validator.signing_key().public_key()),
+            "b3e445d43871965d890a398f719348a1405ac72e35b92727cc570026f54471af7ea7b2040622a8fd0b5bfb2a209b5911"
+        );
+        assert_eq!(
+            hex::encode(validator.withdrawal_key().public_key()),
+            "8ebe599559cbf3abbc6a72b25d8bc13fd9b5075283fcd9ec47b2a0bf6c5148a2e9e615b181e5b2c03abe63818ef70c61"
+        );
+
+        let master = SecretKey::from_mnemonic(&mnemonic, "").unwrap();
+        let signing = master.derive_path(&Validator::signing_key_path(1).unwrap());
+        assert_eq!(signing.to_bytes(), Validator::new(&master, 1).unwrap().signing_key().to_bytes());
+        assert_eq!(
+            hex::encode(signing.public_key()),
+            "aeb399bf5648b0e9980c1731824c269631a41320c3d7f730c40587e1a37a5e1c8b5755fd90080a7b3fb90d3fd419c0a7"
+        );
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        assert!(matches!(SecretKey::derive_master(&[0; 31]), Err(BlsError::SeedTooShort(31))));
+        assert!(matches!(SecretKey::from_bytes(&[0; 32]), Err(BlsError::InvalidSecretKey)));
+        assert!(matches!(SecretKey::from_bytes(&[0xff; 32]), Err(BlsError::InvalidSecretKey)));
+
+        let mut one = [0; 32];
+        one[31] = 1;
+        assert!(SecretKey::from_bytes(&one).is_ok());
+
+        // 2^31 would be printed as the hardened child 0'
+        let master = SecretKey::from_bytes(&one).unwrap();
+        assert!(matches!(Validator::withdrawal_key_path(1 << 31), Err(BlsError::InvalidIndex(0x8000_0000))));
+        assert!(matches!(Validator::new(&master, u32::MAX), Err(BlsError::InvalidIndex(u32::MAX))));
+        assert_eq!(Validator::withdrawal_key_path((1 << 31) - 1).unwrap().to_string(), "m/12381/3600/2147483647/0");
+    }
+}
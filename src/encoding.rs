@@ -0,0 +1,102 @@
+/*
+    Bit-level encoding core shared by generation, parsing and validation.
+
+    Entropy + checksum bits are packed into 11-bit word indexes (and back) with integer shifts,
+    no binary Strings are created, so no copy of the secret is left in the heap.
+    Both functions write into caller-provided buffers and never allocate.
+*/
+
+const WORD_BITS: u32 = 11;
+const WORD_MASK: u32 = (1 << WORD_BITS) - 1; // 0b111_1111_1111 => 2047
+
+/// Packs `entropy` followed by the lowest `checksum_bits` bits of `checksum` into 11-bit word indexes
+///
+/// `indices` must hold exactly `(entropy.len() * 8 + checksum_bits) / 11` elements.
+pub fn entropy_to_indices(entropy: &[u8], checksum: u8, checksum_bits: usize, indices: &mut [u16]) {
+    debug_assert_eq!((entropy.len() * 8 + checksum_bits) % WORD_BITS as usize, 0);
+    debug_assert_eq!(indices.len(), (entropy.len() * 8 + checksum_bits) / WORD_BITS as usize);
+
+    // Bits waiting to be written, never more than 10 + 8 bits are buffered
+    let mut buffer: u32 = 0;
+    let mut buffered_bits: u32 = 0;
+    let mut word_idx = 0;
+
+    let checksum_chunk = (checksum as u32 & ((1 << checksum_bits) - 1), checksum_bits as u32);
+    let entropy_chunks = entropy.iter().map(|byte| (*byte as u32, 8));
+
+    for (bits, bits_count) in entropy_chunks.chain(core::iter::once(checksum_chunk)) {
+        buffer = (buffer << bits_count) | bits;
+        buffered_bits += bits_count;
+
+        while buffered_bits >= WORD_BITS {
+            buffered_bits -= WORD_BITS;
+            indices[word_idx] = ((buffer >> buffered_bits) & WORD_MASK) as u16;
+            word_idx += 1;
+        }
+        buffer &= (1 << buffered_bits) - 1; // Drop the bits already written
+    }
+}
+
+/// Unpacks 11-bit word indexes into `entropy`, returns the remaining checksum bits
+///
+/// `entropy` must hold exactly the number of entropy bytes encoded by `indices` (16 to 32).
+pub fn indices_to_entropy(indices: &[u16], entropy: &mut [u8]) -> u8 {
+    debug_assert!(indices.len() * WORD_BITS as usize >= entropy.len() * 8);
+
+    let mut buffer: u32 = 0;
+    let mut buffered_bits: u32 = 0;
+    let mut byte_idx = 0;
+
+    for index in indices {
+        buffer = (buffer << WORD_BITS) | (*index as u32 & WORD_MASK);
+        buffered_bits += WORD_BITS;
+
+        while buffered_bits >= 8 && byte_idx < entropy.len() {
+            buffered_bits -= 8;
+            entropy[byte_idx] = (buffer >> buffered_bits) as u8;
+            byte_idx += 1;
+        }
+        buffer &= (1 << buffered_bits) - 1;
+    }
+
+    // Only the checksum bits of the last word are left in the buffer (4 to 8 bits)
+    buffer as u8
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_round_trip_for_every_entropy_length() {
+        for bytes_count in [16usize, 20, 24, 28, 32] {
+            let checksum_bits = bytes_count * 8 / 32;
+            let words_count = (bytes_count * 8 + checksum_bits) / 11;
+
+            let entropy: Vec<u8> = (0..bytes_count as u8).map(|byte| byte.wrapping_mul(37)).collect();
+            let checksum = 0b1010_1010 >> (8 - checksum_bits);
+
+            let mut indices = vec![0u16; words_count];
+            entropy_to_indices(&entropy, checksum, checksum_bits, &mut indices);
+            assert!(indices.iter().all(|index| *index < 2048));
+
+            let mut decoded = vec![0u8; bytes_count];
+            let decoded_checksum = indices_to_entropy(&indices, &mut decoded);
+
+            assert_eq!(decoded, entropy);
+            assert_eq!(decoded_checksum, checksum);
+        }
+    }
+
+    #[test]
+    fn test_known_indices() {
+        // 0x7f * 16 with checksum 0b1000 => "legal winner thank year wave sausage worth useful legal winner thank yellow"
+        let mut indices = [0u16; 12];
+        entropy_to_indices(&[0x7f; 16], 0b1000, 4, &mut indices);
+
+        assert_eq!(indices, [1019, 2015, 1790, 2039, 1983, 1533, 2031, 1919, 1019, 2015, 1790, 2040]);
+    }
+}
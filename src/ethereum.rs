@@ -0,0 +1,214 @@
+/*
+    Ethereum accounts derived from the BIP-39 seed, the same way as MetaMask and Ledger Live.
+
+    MetaMask (and most EVM wallets) puts account i at m/44'/60'/0'/0/i, Ledger Live at m/44'/60'/i'/0/0,
+    so only account 0 is the same in both. Its address is the last 20 bytes of the Keccak-256 hash
+    of the uncompressed public key (x || y, without the 0x04 prefix), printed with the EIP-55 checksum casing:
+    a hex letter is uppercase when the matching nibble of Keccak-256(lowercase hex address) is 8 or more.
+*/
+
+use crate::bip32::{Bip32Error, ChildNumber, DerivationPath, ExtendedPrivKey, Network};
+use crate::Mnemonic;
+use alloc::string::String;
+use alloc::vec::Vec;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::PublicKey;
+use sha3::{Digest, Keccak256};
+use zeroize::Zeroizing;
+
+/// Registered SLIP-44 coin type of Ether
+pub const COIN_TYPE: u32 = 60;
+
+/// Path layout of the accounts of a wallet
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// m/44'/60'/0'/0/index, MetaMask, Trezor and most EVM wallets
+    MetaMask,
+    /// m/44'/60'/index'/0/0, Ledger Live
+    LedgerLive,
+}
+
+impl Preset {
+    /// Path of account `index`
+    pub fn path(&self, index: u32) -> Result<DerivationPath, Bip32Error> {
+        Ok(self.root_path().extend(&self.account_path(index)?))
+    }
+
+    // Deepest key shared by every account, derived once by the wallet
+    fn root_path(&self) -> DerivationPath {
+        let root = DerivationPath::from(alloc::vec![ChildNumber::Hardened(44), ChildNumber::Hardened(COIN_TYPE)]);
+
+        match self {
+            Preset::MetaMask => root.child(ChildNumber::Hardened(0)).child(ChildNumber::Normal(0)),
+            Preset::LedgerLive => root,
+        }
+    }
+
+    fn account_path(&self, index: u32) -> Result<DerivationPath, Bip32Error> {
+        Ok(DerivationPath::from(match self {
+            Preset::MetaMask => alloc::vec![ChildNumber::normal(index)?],
+            Preset::LedgerLive => {
+                alloc::vec![ChildNumber::hardened(index)?, ChildNumber::Normal(0), ChildNumber::Normal(0)]
+            }
+        }))
+    }
+}
+
+/// Key shared by every account of a wallet: m/44'/60'/0'/0 for MetaMask, m/44'/60' for Ledger Live
+pub struct Wallet {
+    preset: Preset,
+    root: ExtendedPrivKey,
+}
+
+impl Wallet {
+    /// Derives the wallet from a BIP-32 master key
+    pub fn new(master: &ExtendedPrivKey, preset: Preset) -> Result<Wallet, Bip32Error> {
+        Ok(Wallet { preset, root: master.derive_path(&preset.root_path())? })
+    }
+
+    /// Derives the wallet from the seed of a mnemonic and its passphrase
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str, preset: Preset) -> Result<Wallet, Bip32Error> {
+        // Version bytes are never serialized, any network gives the same keys
+        Self::new(&ExtendedPrivKey::from_mnemonic(mnemonic, passphrase, Network::Mainnet)?, preset)
+    }
+
+    pub fn preset(&self) -> Preset {
+        self.preset
+    }
+
+    /// Account `index`, at m/44'/60'/0'/0/index (MetaMask) or m/44'/60'/index'/0/0 (Ledger Live)
+    pub fn account(&self, index: u32) -> Result<Account, Bip32Error> {
+        let key = self.root.derive_path(&self.preset.account_path(index)?)?;
+        Ok(Account { preset: self.preset, index, key })
+    }
+
+    /// First `count` accounts, in the order the wallet adds them
+    pub fn accounts(&self, count: u32) -> Result<Vec<Account>, Bip32Error> {
+        (0..count).map(|index| self.account(index)).collect()
+    }
+}
+
+/// Ethereum account: a secp256k1 key pair and its address
+pub struct Account {
+    preset: Preset,
+    index: u32,
+    key: ExtendedPrivKey,
+}
+
+impl Account {
+    /// Number of the account in the wallet
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Path of the account key, e.g. m/44'/60'/0'/0/1 (MetaMask) or m/44'/60'/1'/0/0 (Ledger Live)
+    pub fn path(&self) -> DerivationPath {
+        self.preset.path(self.index).expect("Index is checked when the account is created")
+    }
+
+    /// Address with the EIP-55 checksum casing, e.g. 0x9858EfFD232B4033E47d90003D41EC34EcaEda94
+    pub fn address(&self) -> String {
+        address(&self.key.public_key())
+    }
+
+    /// Private key as 64 hex characters without the 0x prefix, the format MetaMask exports and imports
+    pub fn private_key_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(self.key.private_key_bytes().as_slice()))
+    }
+
+    /// BIP-32 key of the account, for signing with k256
+    pub fn key(&self) -> &ExtendedPrivKey {
+        &self.key
+    }
+}
+
+/// Checksummed address of a secp256k1 public key
+pub fn address(public_key: &PublicKey) -> String {
+    let point = public_key.to_encoded_point(false);
+    let hash = Keccak256::digest(&point.as_bytes()[1..]);
+
+    checksum_address(hash[12..].try_into().expect("Address is the last 20 bytes of the hash"))
+}
+
+/// Formats a 20-byte address as 0x-prefixed hex with the EIP-55 checksum casing
+pub fn checksum_address(address: &[u8; 20]) -> String {
+    let lowercase = hex::encode(address);
+    let hash = Keccak256::digest(lowercase.as_bytes());
+
+    let mut checksummed = String::with_capacity(42);
+    checksummed.push_str("0x");
+    for (position, character) in lowercase.chars().enumerate() {
+        // Nibble `position` of the hash, high nibble first
+        let nibble = (hash[position / 2] >> (4 * (1 - position % 2))) & 0x0f;
+        checksummed.push(if nibble >= 8 { character.to_ascii_uppercase() } else { character });
+    }
+    checksummed
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+    use alloc::string::ToString;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_metamask_accounts() {
+        let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).unwrap();
+        let accounts = Wallet::from_mnemonic(&mnemonic, "", Preset::MetaMask).unwrap().accounts(3).unwrap();
+
+        let expected = [
+            ("0x9858EfFD232B4033E47d90003D41EC34EcaEda94", "1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727"),
+            ("0x6Fac4D18c912343BF86fa7049364Dd4E424Ab9C0", "9a983cb3d832fbde5ab49d692b7a8bf5b5d232479c99333d0fc8e1d21f1b55b6"),
+            ("0xb6716976A3ebe8D39aCEB04372f22Ff8e6802D7A", "5b824bd1104617939cd07c117ddc4301eb5beeca0904f964158963d69ab9d831"),
+        ];
+        for (account, (address, private_key)) in accounts.iter().zip(expected) {
+            assert_eq!(account.address(), address);
+            assert_eq!(account.private_key_hex().as_str(), private_key);
+        }
+        assert_eq!(accounts[2].path().to_string(), "m/44'/60'/0'/0/2");
+    }
+
+    #[test]
+    fn test_ledger_live_accounts() {
+        let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).unwrap();
+        let accounts = Wallet::from_mnemonic(&mnemonic, "", Preset::LedgerLive).unwrap().accounts(3).unwrap();
+
+        let expected = [
+            ("0x9858EfFD232B4033E47d90003D41EC34EcaEda94", "1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727"),
+            ("0x78839F6054d7ed13918bAe0473BA31b1Ca9D7265", "318470c858f622e48a80120a1fc3c8460d67a7bf31b3273a6d27d4c013f2f8d3"),
+            ("0x07B5FdfEB4E11826D233403Fe8Db0611CCF4c231", "4b9aad43cd664b970876f6cf635fe1526819234dec28c4219fc4300a0312de8c"),
+        ];
+        for (account, (address, private_key)) in accounts.iter().zip(expected) {
+            assert_eq!(account.address(), address);
+            assert_eq!(account.private_key_hex().as_str(), private_key);
+        }
+        assert_eq!(accounts[1].path().to_string(), "m/44'/60'/1'/0/0");
+    }
+
+    #[test]
+    fn test_eip55_vectors() {
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+            "0x52908400098527886E0F7030069857D2E4169EE7",
+            "0xde709f2102306220921060314715629080e2fb77",
+        ] {
+            let bytes: [u8; 20] = hex::decode(&address[2..]).unwrap().try_into().unwrap();
+            assert_eq!(checksum_address(&bytes), address);
+        }
+    }
+
+    #[test]
+    fn test_hardened_index_is_rejected() {
+        let master = ExtendedPrivKey::new_master(Network::Mainnet, &[1; 32]).unwrap();
+        for preset in [Preset::MetaMask, Preset::LedgerLive] {
+            let wallet = Wallet::new(&master, preset).unwrap();
+            assert_eq!(wallet.account(1 << 31).err(), Some(Bip32Error::InvalidIndex(1 << 31)));
+        }
+    }
+}
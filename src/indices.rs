@@ -0,0 +1,212 @@
+/*
+    Fixed-size mnemonic for firmware that must not touch the heap.
+
+    The phrase is kept as N 11-bit word indexes on the stack, words are looked up in a `&'static` wordlist
+    only when they are rendered. Entropy only lives in stack buffers that are wiped after use.
+*/
+
+use crate::{encoding, Mnemonic, MnemonicError, MnemonicType, WordList};
+use alloc::string::ToString;
+use rand_core::{CryptoRng, RngCore};
+use zeroize::{Zeroize, Zeroizing};
+
+const MAX_ENTROPY_BYTES: usize = 32;
+
+/// Mnemonic stored as `N` word indexes (12, 15, 18, 21 or 24) plus a reference to its wordlist, without heap allocations
+pub struct MnemonicIndices<const N: usize> {
+    indices: [u16; N],
+    wordlist: &'static dyn WordList,
+}
+
+impl<const N: usize> Drop for MnemonicIndices<N> {
+    /// Wipes the word indexes, they encode the entropy
+    fn drop(&mut self) {
+        self.indices.zeroize();
+    }
+}
+
+impl<const N: usize> MnemonicIndices<N> {
+    /// Generates a new mnemonic of `N` words with entropy drawn from the given cryptographically secure RNG
+    pub fn generate_with_rng<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        wordlist: &'static dyn WordList,
+    ) -> Result<MnemonicIndices<N>, MnemonicError> {
+        let mnemonic_type = Self::mnemonic_type()?;
+
+        let mut entropy = Zeroizing::new([0u8; MAX_ENTROPY_BYTES]);
+        let entropy = &mut entropy[..mnemonic_type.bytes()];
+        rng.try_fill_bytes(entropy).map_err(MnemonicError::GeneratorError)?;
+
+        Self::from_entropy(entropy, wordlist)
+    }
+
+    /// Creates a mnemonic of `N` words from caller-supplied entropy bytes
+    pub fn from_entropy(entropy: &[u8], wordlist: &'static dyn WordList) -> Result<MnemonicIndices<N>, MnemonicError> {
+        let mnemonic_type = Self::mnemonic_type()?;
+        if entropy.len() != mnemonic_type.bytes() {
+            return Err(MnemonicError::BadEntropyLength(entropy.len()));
+        }
+
+        let mut indices = [0u16; N];
+        let checksum = Mnemonic::generate_checksum(entropy, mnemonic_type);
+        encoding::entropy_to_indices(entropy, checksum, mnemonic_type.checksum_bits(), &mut indices);
+
+        Ok(MnemonicIndices { indices, wordlist })
+    }
+
+    /// Parses a phrase of exactly `N` words and verifies its checksum
+    ///
+    /// Unlike `Mnemonic::from_phrase` abbreviations are not accepted. With a built-in `Language`, words already in
+    /// NFKD (e.g. ASCII English words) are looked up without allocating. A `CustomWordList` normalizes every word
+    /// into a new string, and an `UnknownWord` error copies the word.
+    pub fn from_phrase(phrase: &str, wordlist: &'static dyn WordList) -> Result<MnemonicIndices<N>, MnemonicError> {
+        let mnemonic_type = Self::mnemonic_type()?;
+
+        // Wiped on every early return, only the copy moved into the mnemonic survives
+        let mut indices = Zeroizing::new([0u16; N]);
+        let mut count = 0;
+
+        for (position, word) in phrase.split_whitespace().enumerate() {
+            if position >= N {
+                return Err(MnemonicError::BadWordCount(phrase.split_whitespace().count()));
+            }
+            indices[position] = wordlist.index_of(word)
+                .ok_or_else(|| MnemonicError::UnknownWord { index: position, word: word.to_string() })?;
+            count += 1;
+        }
+
+        if count != N {
+            return Err(MnemonicError::BadWordCount(count));
+        }
+
+        let mnemonic = MnemonicIndices { indices: *indices, wordlist };
+        let (expected, found) = mnemonic.checksums(mnemonic_type);
+        if expected != found {
+            return Err(MnemonicError::InvalidChecksum { expected, found });
+        }
+
+        Ok(mnemonic)
+    }
+
+    /// Converts a heap-allocated mnemonic, the words are encoded again with `wordlist`
+    pub fn from_mnemonic(mnemonic: &Mnemonic, wordlist: &'static dyn WordList) -> Result<MnemonicIndices<N>, MnemonicError> {
+        Self::from_entropy(mnemonic.entropy(), wordlist)
+    }
+
+    /// Converts to a heap-allocated `Mnemonic` with the same entropy and wordlist
+    pub fn to_mnemonic(&self) -> Mnemonic {
+        let mnemonic_type = MnemonicType::from_words_count(N).expect("N is checked when the mnemonic is created");
+
+        let mut entropy = Zeroizing::new([0u8; MAX_ENTROPY_BYTES]);
+        let entropy = &mut entropy[..mnemonic_type.bytes()];
+        encoding::indices_to_entropy(&self.indices, entropy);
+
+        Mnemonic::from_entropy(entropy, self.wordlist).expect("Entropy of a mnemonic is always valid")
+    }
+
+    /// Recomputes the checksum from the entropy encoded in the indexes and compares it with the one in the last word
+    pub fn validate_checksum(&self) -> bool {
+        let mnemonic_type = MnemonicType::from_words_count(N).expect("N is checked when the mnemonic is created");
+        let (expected, found) = self.checksums(mnemonic_type);
+        expected == found
+    }
+
+    /// Word indexes (0 to 2047) of the phrase
+    pub fn indices(&self) -> &[u16; N] {
+        &self.indices
+    }
+
+    /// Words of the phrase, one by one, borrowed from the wordlist
+    pub fn words(&self) -> impl Iterator<Item = &'static str> + '_ {
+        let wordlist = self.wordlist;
+        self.indices.iter().map(move |index| wordlist.word_at(*index).expect("Word index is always 11 bits"))
+    }
+
+    /// Wordlist used to render the words
+    pub fn wordlist(&self) -> &'static dyn WordList {
+        self.wordlist
+    }
+
+    fn mnemonic_type() -> Result<MnemonicType, MnemonicError> {
+        MnemonicType::from_words_count(N).ok_or(MnemonicError::BadWordCount(N))
+    }
+
+    fn checksums(&self, mnemonic_type: MnemonicType) -> (u8, u8) {
+        // Returns (checksum computed from the entropy, checksum found in the last word)
+        let mut entropy = Zeroizing::new([0u8; MAX_ENTROPY_BYTES]);
+        let entropy = &mut entropy[..mnemonic_type.bytes()];
+        let found = encoding::indices_to_entropy(&self.indices, entropy);
+
+        (Mnemonic::generate_checksum(entropy, mnemonic_type), found)
+    }
+}
+
+impl<const N: usize> From<&MnemonicIndices<N>> for Mnemonic {
+    fn from(mnemonic: &MnemonicIndices<N>) -> Mnemonic {
+        mnemonic.to_mnemonic()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+    use alloc::format;
+    use alloc::string::String;
+
+    const PHRASE: &str = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+
+    #[test]
+    fn test_from_entropy_and_words() {
+        let mnemonic = MnemonicIndices::<12>::from_entropy(&[0x7f; 16], &Language::English).unwrap();
+
+        assert_eq!(mnemonic.indices(), &[1019, 2015, 1790, 2039, 1983, 1533, 2031, 1919, 1019, 2015, 1790, 2040]);
+        assert!(mnemonic.words().eq(PHRASE.split(' ')));
+        assert!(mnemonic.validate_checksum());
+    }
+
+    #[test]
+    fn test_from_phrase() {
+        let mnemonic = MnemonicIndices::<12>::from_phrase(PHRASE, &Language::English).unwrap();
+        assert_eq!(mnemonic.indices()[11], 2040);
+
+        let tampered = PHRASE.replace("yellow", "year");
+        assert!(matches!(
+            MnemonicIndices::<12>::from_phrase(&tampered, &Language::English),
+            Err(MnemonicError::InvalidChecksum { .. })
+        ));
+        assert!(matches!(
+            MnemonicIndices::<24>::from_phrase(PHRASE, &Language::English),
+            Err(MnemonicError::BadWordCount(12))
+        ));
+        assert!(matches!(
+            MnemonicIndices::<12>::from_phrase(&format!("{} legal", PHRASE), &Language::English),
+            Err(MnemonicError::BadWordCount(13))
+        ));
+        assert!(matches!(
+            MnemonicIndices::<13>::from_entropy(&[0x7f; 16], &Language::English),
+            Err(MnemonicError::BadWordCount(13))
+        ));
+        assert!(matches!(
+            MnemonicIndices::<15>::from_entropy(&[0x7f; 16], &Language::English),
+            Err(MnemonicError::BadEntropyLength(16))
+        ));
+    }
+
+    #[test]
+    fn test_conversion_with_mnemonic() {
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        let mut rng = ChaCha20Rng::from_seed([7u8; 32]);
+        let fixed = MnemonicIndices::<24>::generate_with_rng(&mut rng, &Language::Japanese).unwrap();
+
+        let mnemonic = Mnemonic::from(&fixed);
+        assert!(mnemonic.mnemonic_phrase().iter().map(String::as_str).eq(fixed.words()));
+        assert_eq!(mnemonic.wordlist().language(), Some(Language::Japanese));
+
+        let back = MnemonicIndices::<24>::from_mnemonic(&mnemonic, &Language::Japanese).unwrap();
+        assert_eq!(back.indices(), fixed.indices());
+    }
+}
@@ -1,4 +1,4 @@
-pub const WORDS: [&str; 2048] = [
+pub static WORDS: [&str; 2048] = [
 	"的",
 	"一",
 	"是",
@@ -2048,3 +2048,135 @@ pub const WORDS: [&str; 2048] = [
 	"矮",
 	"歇",
 ];
+
+/// Indexes of WORDS sorted by the bytes of each word, used for binary search lookups
+pub static SORTED_INDEXES: [u16; 2048] = [
+	1, 1092, 274, 326, 1731, 64, 13, 44, 4, 131, 471, 376, 282, 2033, 1572, 98,
+	1950, 277, 708, 1924, 90, 496, 1856, 14, 10, 849, 1670, 942, 1448, 12, 36, 1415,
+	528, 1597, 764, 147, 122, 65, 1567, 834, 1470, 861, 1948, 1229, 777, 241, 42, 415,
+	941, 388, 877, 831, 1560, 5, 1082, 285, 123, 80, 28, 1764, 734, 639, 189, 879,
+	541, 112, 1149, 292, 1087, 48, 1295, 1608, 475, 1863, 910, 451, 8, 1239, 295, 1563,
+	570, 1968, 357, 846, 713, 97, 1478, 1761, 19, 1876, 1166, 178, 669, 17, 1275, 23,
+	1785, 1466, 208, 495, 255, 1066, 1411, 521, 992, 1404, 1293, 1541, 1315, 387, 688, 1230,
+	34, 966, 360, 900, 1509, 1810, 844, 1727, 1569, 1043, 605, 151, 195, 384, 482, 91,
+	430, 606, 1753, 26, 142, 1967, 95, 322, 549, 687, 1605, 1032, 1700, 760, 423, 887,
+	1245, 1763, 266, 348, 1983, 610, 884, 692, 449, 1010, 1743, 1904, 439, 1127, 857, 1108,
+	249, 739, 1304, 1556, 1819, 1739, 1915, 1531, 1379, 1265, 1685, 392, 1475, 355, 1496, 584,
+	252, 263, 429, 873, 186, 196, 127, 340, 169, 297, 852, 298, 132, 604, 585, 111,
+	325, 1109, 463, 1483, 137, 1842, 305, 1645, 447, 172, 238, 1658, 1159, 1936, 1279, 666,
+	245, 356, 1840, 701, 1679, 1210, 437, 1701, 555, 1198, 240, 1192, 1631, 1669, 959, 1940,
+	1844, 29, 601, 1086, 1060, 31, 311, 1409, 721, 444, 414, 795, 254, 824, 641, 640,
+	587, 148, 230, 1955, 25, 92, 1748, 1299, 749, 706, 1283, 114, 1972, 1197, 952, 1383,
+	1742, 661, 1144, 73, 1709, 411, 628, 87, 324, 1941, 39, 636, 1016, 1428, 1559, 422,
+	532, 1712, 1264, 1285, 1774, 1400, 1725, 1232, 480, 77, 268, 242, 790, 63, 404, 637,
+	1153, 410, 342, 709, 309, 1074, 260, 1493, 618, 1063, 1951, 738, 747, 938, 265, 470,
+	1636, 964, 1875, 2040, 474, 1744, 427, 1805, 280, 1267, 932, 145, 100, 485, 488, 146,
+	205, 716, 674, 141, 37, 1771, 256, 293, 159, 1828, 1663, 2022, 276, 647, 848, 553,
+	161, 375, 999, 35, 535, 420, 619, 566, 440, 589, 1813, 621, 133, 107, 1562, 1539,
+	40, 343, 53, 1715, 1778, 155, 793, 1135, 2043, 684, 761, 1103, 520, 492, 1327, 1165,
+	635, 1178, 1888, 946, 1587, 1330, 533, 193, 518, 405, 766, 1892, 926, 157, 7, 1954,
+	1745, 1396, 2016, 201, 1022, 416, 956, 1804, 735, 1461, 1321, 1181, 1305, 1006, 798, 395,
+	796, 1646, 1227, 1542, 813, 1397, 868, 1421, 1249, 1793, 1216, 279, 118, 253, 109, 424,
+	1179, 871, 543, 565, 15, 220, 479, 1084, 361, 1514, 3, 27, 319, 563, 645, 695,
+	1824, 705, 607, 2029, 1271, 1187, 1846, 902, 1859, 458, 1928, 1399, 1718, 612, 1442, 1018,
+	850, 213, 1017, 1095, 1974, 2018, 1120, 1056, 1671, 1050, 1529, 745, 1262, 284, 1405, 1150,
+	1028, 562, 1343, 335, 1495, 258, 409, 390, 1076, 115, 54, 720, 614, 11, 116, 436,
+	613, 741, 505, 212, 1370, 991, 1057, 1752, 1132, 978, 1464, 1412, 906, 1665, 510, 1226,
+	1430, 231, 103, 75, 1161, 778, 1783, 1917, 2041, 1469, 1528, 1733, 469, 1360, 1223, 1408,
+	406, 1881, 1938, 1900, 1913, 929, 927, 1545, 1213, 2000, 1659, 46, 752, 546, 448, 936,
+	1779, 957, 1777, 57, 809, 837, 106, 1735, 786, 318, 1183, 338, 970, 500, 55, 904,
+	1152, 86, 802, 699, 672, 755, 912, 1359, 578, 1902, 71, 391, 896, 1588, 1996, 1443,
+	523, 622, 1440, 742, 1033, 1251, 32, 1837, 1511, 278, 1647, 552, 664, 226, 1352, 83,
+	219, 506, 1326, 1154, 1128, 2039, 1364, 30, 1843, 1093, 894, 662, 1277, 486, 467, 922,
+	1781, 1444, 940, 2014, 188, 478, 221, 948, 1792, 1307, 974, 1667, 1218, 1644, 1290, 1741,
+	972, 680, 1504, 41, 574, 1494, 1185, 1554, 593, 180, 629, 2030, 1291, 428, 389, 542,
+	899, 1905, 663, 317, 519, 538, 197, 1651, 1021, 1425, 248, 124, 38, 182, 1384, 1963,
+	1350, 365, 1220, 913, 1051, 788, 990, 104, 498, 1248, 1677, 434, 1206, 70, 921, 1039,
+	1005, 937, 1351, 167, 105, 872, 1557, 1355, 202, 1820, 491, 1142, 347, 854, 1427, 1347,
+	975, 1041, 243, 998, 94, 1007, 125, 1073, 1237, 2038, 382, 1626, 1035, 1627, 425, 866,
+	648, 756, 173, 374, 1169, 1331, 61, 2002, 1853, 1366, 677, 501, 1926, 140, 250, 1381,
+	1549, 281, 1335, 1078, 1488, 1870, 452, 711, 1117, 988, 551, 558, 1595, 882, 289, 797,
+	102, 2045, 1363, 199, 1515, 1822, 1231, 1614, 1196, 780, 1516, 1053, 1770, 1628, 1958, 1707,
+	1170, 1606, 1506, 174, 1119, 1784, 1965, 1966, 1879, 1228, 179, 1122, 166, 421, 1592, 801,
+	1935, 881, 1932, 1439, 1998, 1538, 33, 16, 150, 251, 1201, 1454, 1054, 693, 59, 1757,
+	232, 345, 1234, 1861, 312, 1118, 1726, 859, 781, 1420, 897, 1699, 1534, 1760, 524, 683,
+	667, 408, 101, 1834, 923, 644, 642, 1044, 1546, 1789, 1492, 588, 336, 1519, 1437, 1241,
+	1952, 1088, 853, 1920, 372, 1914, 1395, 1684, 1452, 1338, 1164, 1716, 1734, 1387, 1487, 1077,
+	750, 787, 385, 1253, 239, 461, 1447, 1418, 1929, 1555, 784, 908, 1702, 1984, 1883, 2004,
+	1141, 1979, 771, 649, 257, 1340, 945, 821, 548, 1607, 982, 223, 679, 512, 1720, 949,
+	1961, 1918, 1367, 183, 1217, 851, 1156, 1302, 2012, 1860, 753, 1722, 1815, 1681, 1071, 1171,
+	2011, 1959, 1348, 1548, 2006, 1899, 1772, 1530, 1001, 901, 1297, 418, 300, 302, 825, 244,
+	117, 611, 453, 697, 1601, 1195, 314, 1034, 759, 1462, 138, 2025, 353, 198, 1906, 337,
+	191, 1037, 808, 1749, 399, 454, 135, 52, 554, 1137, 1276, 1061, 442, 1106, 170, 736,
+	119, 1714, 767, 1908, 576, 1857, 1686, 20, 1775, 1927, 1886, 1212, 143, 1944, 464, 875,
+	917, 623, 1809, 2, 513, 1851, 2007, 1481, 829, 1632, 775, 928, 931, 1441, 1573, 1578,
+	1047, 1143, 2036, 608, 308, 1801, 1756, 654, 1124, 176, 168, 6, 1324, 581, 1219, 522,
+	577, 234, 537, 650, 911, 99, 290, 1123, 1732, 93, 835, 632, 299, 981, 534, 547,
+	733, 1497, 950, 160, 21, 977, 1880, 1706, 1823, 869, 595, 291, 433, 704, 373, 190,
+	1376, 1019, 1841, 909, 1964, 591, 878, 1523, 456, 1257, 1502, 1272, 1576, 1329, 446, 1386,
+	556, 758, 1303, 130, 757, 235, 462, 1704, 1512, 1759, 1769, 560, 1473, 1791, 1997, 1160,
+	1059, 1429, 1456, 1638, 820, 634, 898, 1641, 1694, 1833, 1375, 1767, 779, 1130, 1100, 880,
+	1558, 768, 1013, 1575, 200, 819, 1850, 1030, 1406, 1887, 962, 2047, 822, 559, 139, 158,
+	363, 564, 1921, 507, 1146, 1158, 968, 369, 2009, 1438, 1808, 727, 327, 1067, 149, 1296,
+	450, 774, 1358, 60, 153, 905, 531, 1288, 1482, 1064, 76, 969, 1891, 210, 1316, 626,
+	1252, 457, 1136, 1424, 1313, 1934, 732, 1690, 856, 823, 1378, 162, 2019, 550, 1776, 288,
+	267, 979, 1650, 58, 1098, 1309, 568, 1068, 544, 1333, 1349, 1336, 1975, 1026, 1431, 717,
+	1070, 1590, 1339, 1260, 1254, 748, 203, 466, 216, 1571, 1385, 1754, 707, 286, 986, 1624,
+	1925, 1300, 1167, 275, 1649, 1602, 432, 1214, 1799, 1971, 1020, 1874, 499, 1613, 1325, 1991,
+	400, 726, 1693, 303, 817, 1460, 1796, 1780, 1446, 359, 1292, 763, 769, 1985, 1190, 1046,
+	586, 2035, 1949, 557, 983, 1266, 484, 1391, 1982, 1640, 1524, 1885, 1689, 1656, 815, 1755,
+	1403, 1114, 751, 1507, 481, 996, 947, 1072, 993, 1432, 973, 1639, 1705, 1423, 1157, 1320,
+	96, 1202, 1564, 1806, 740, 1995, 864, 1903, 743, 272, 1259, 1890, 1402, 1848, 113, 841,
+	487, 1812, 1993, 1458, 1603, 1024, 832, 1898, 1574, 1258, 1617, 573, 816, 1115, 1099, 1453,
+	468, 1208, 1579, 1388, 915, 1662, 1318, 84, 1615, 1491, 207, 1847, 599, 472, 1611, 1717,
+	1786, 668, 1829, 1525, 1105, 1080, 2034, 441, 953, 460, 1620, 673, 85, 1184, 1471, 1373,
+	843, 529, 81, 1365, 1362, 1240, 1570, 1049, 1544, 1585, 1308, 754, 1800, 24, 22, 596,
+	110, 762, 1472, 72, 1177, 627, 2021, 371, 652, 1633, 698, 1750, 1654, 1692, 1357, 1543,
+	2015, 1990, 1782, 503, 1811, 2028, 1129, 1852, 1281, 313, 270, 0, 1604, 886, 729, 2020,
+	1675, 1946, 686, 715, 1140, 1012, 1795, 1002, 1207, 933, 328, 184, 126, 827, 413, 1691,
+	144, 323, 459, 67, 1211, 1310, 907, 1973, 833, 224, 1505, 728, 2046, 366, 403, 1455,
+	1536, 1986, 370, 1565, 540, 690, 1653, 1696, 811, 1081, 344, 1485, 1138, 1703, 1500, 1451,
+	1199, 1029, 883, 1134, 1322, 333, 1390, 121, 885, 1622, 516, 1501, 1510, 2032, 1205, 876,
+	341, 1243, 1015, 903, 49, 346, 1623, 1301, 1566, 1112, 1580, 1635, 332, 435, 722, 1468,
+	187, 1334, 994, 776, 1261, 1657, 1839, 1695, 377, 1374, 354, 858, 694, 1353, 1970, 177,
+	657, 1593, 1023, 575, 1311, 655, 1377, 616, 1014, 154, 1817, 68, 1683, 1052, 1215, 860,
+	646, 1643, 1678, 1532, 658, 383, 206, 2003, 1174, 1089, 1583, 1600, 352, 339, 696, 830,
+	1126, 1596, 1994, 807, 438, 1589, 1011, 171, 407, 1111, 572, 1535, 1107, 1083, 1609, 489,
+	1125, 417, 218, 685, 1807, 1009, 1233, 1354, 1040, 1250, 1827, 1180, 789, 1393, 1204, 136,
+	592, 227, 1845, 490, 380, 730, 963, 62, 1729, 163, 1319, 1672, 261, 1832, 703, 222,
+	603, 1323, 1598, 579, 1380, 443, 1163, 930, 1957, 1209, 914, 1372, 1407, 865, 1933, 1490,
+	791, 1831, 1246, 714, 1401, 1394, 1977, 515, 465, 1637, 1668, 304, 364, 1873, 891, 1826,
+	1943, 211, 643, 175, 51, 951, 1186, 1075, 1236, 624, 294, 731, 1489, 892, 1803, 1882,
+	2005, 1306, 812, 1599, 1270, 473, 1787, 1660, 1581, 874, 1980, 702, 925, 960, 939, 1553,
+	1923, 43, 1113, 1867, 1242, 1797, 1027, 870, 810, 867, 1969, 1225, 1765, 1371, 1916, 1521,
+	1274, 1368, 1835, 1547, 1238, 79, 329, 594, 1317, 1866, 1247, 1433, 987, 419, 1568, 617,
+	2042, 681, 1449, 262, 517, 350, 1661, 316, 1269, 1457, 1981, 504, 971, 746, 719, 1435,
+	530, 602, 1816, 805, 665, 1520, 1273, 1188, 836, 1048, 1298, 2024, 2010, 804, 1008, 765,
+	1989, 525, 1894, 1244, 536, 1922, 1910, 1976, 1728, 1268, 967, 1740, 1337, 2017, 1836, 1038,
+	1747, 1222, 1467, 1036, 1346, 1025, 631, 1550, 1664, 855, 1551, 1674, 1477, 818, 56, 1221,
+	934, 895, 889, 128, 1794, 700, 1422, 1398, 247, 1682, 1003, 924, 381, 2001, 246, 18,
+	1768, 228, 378, 271, 569, 1930, 625, 233, 164, 916, 561, 1162, 229, 1176, 296, 828,
+	678, 800, 334, 1498, 367, 508, 362, 236, 1436, 204, 1055, 301, 890, 476, 710, 1148,
+	1648, 582, 920, 1499, 351, 493, 1582, 1942, 783, 799, 47, 675, 1426, 1937, 842, 958,
+	862, 483, 514, 1939, 1102, 1788, 826, 1131, 1586, 1172, 1116, 192, 1710, 1625, 1450, 600,
+	1280, 620, 656, 1919, 1182, 770, 152, 1931, 1345, 1133, 1389, 1145, 1522, 839, 1655, 1224,
+	571, 1255, 214, 1751, 1618, 1798, 997, 1173, 1459, 1486, 331, 1901, 954, 989, 82, 676,
+	379, 1630, 1518, 590, 1445, 651, 737, 630, 1416, 217, 955, 893, 1584, 320, 1945, 321,
+	1278, 1294, 307, 539, 1263, 1849, 682, 580, 691, 225, 1680, 1527, 1634, 1591, 1719, 1736,
+	609, 2026, 1342, 1724, 1878, 2008, 1235, 215, 1711, 330, 1897, 744, 45, 1865, 1097, 237,
+	402, 1762, 108, 9, 66, 545, 1369, 398, 1652, 847, 659, 1577, 1062, 1094, 1069, 615,
+	477, 1286, 1619, 445, 1058, 724, 1537, 995, 181, 315, 269, 1830, 2037, 1698, 1992, 1065,
+	1031, 156, 1121, 1168, 1474, 1101, 1862, 1561, 120, 1382, 1896, 1988, 1552, 1889, 1517, 69,
+	1289, 89, 509, 785, 1871, 1864, 2013, 1673, 1868, 1818, 426, 1909, 1610, 306, 1147, 74,
+	134, 1104, 88, 283, 1434, 794, 1814, 1758, 840, 1392, 653, 2027, 660, 1284, 1687, 494,
+	1907, 1465, 1960, 1079, 1413, 782, 1312, 1513, 1419, 919, 1612, 1194, 1344, 1480, 718, 1676,
+	1821, 1855, 1854, 1191, 1858, 984, 1341, 209, 264, 1256, 1189, 165, 1869, 129, 1953, 1533,
+	1155, 1314, 2023, 1802, 1526, 259, 527, 638, 1091, 1090, 287, 773, 671, 803, 394, 943,
+	712, 597, 670, 1737, 455, 431, 1004, 1877, 1621, 1540, 1328, 1476, 502, 1484, 1175, 1594,
+	1287, 1356, 401, 1096, 1895, 358, 1912, 1738, 792, 1085, 976, 918, 1730, 368, 1361, 1790,
+	1978, 1987, 980, 1616, 412, 888, 396, 725, 50, 194, 1766, 2044, 1697, 386, 1503, 689,
+	526, 863, 393, 1872, 944, 1151, 772, 273, 1962, 723, 1463, 185, 1332, 961, 310, 1884,
+	633, 583, 985, 1721, 1893, 1410, 1042, 1911, 2031, 1110, 1708, 497, 845, 349, 1642, 1999,
+	1746, 1193, 1956, 1723, 397, 1947, 1773, 1838, 1139, 78, 1414, 1479, 567, 838, 806, 1688,
+	935, 1666, 1629, 1713, 1045, 1203, 511, 1282, 598, 1417, 1000, 1825, 965, 1200, 1508, 814,
+];
@@ -1,4 +1,4 @@
-pub const WORDS: [&str; 2048] = [
+pub static WORDS: [&str; 2048] = [
 	"的",
 	"一",
 	"是",
@@ -2048,3 +2048,135 @@ pub const WORDS: [&str; 2048] = [
 	"矮",
 	"歇",
 ];
+
+/// Indexes of WORDS sorted by the bytes of each word, used for binary search lookups
+pub static SORTED_INDEXES: [u16; 2048] = [
+	1, 1092, 274, 1731, 64, 13, 44, 4, 376, 282, 2033, 1572, 1924, 182, 10, 1670,
+	1448, 36, 1597, 764, 65, 834, 1470, 1229, 777, 241, 42, 1560, 831, 5, 1082, 123,
+	80, 639, 189, 879, 112, 541, 1149, 292, 1087, 1608, 475, 1863, 910, 8, 295, 1563,
+	1968, 357, 846, 713, 1761, 19, 1876, 1166, 178, 669, 17, 1785, 1466, 208, 255, 1066,
+	1411, 521, 992, 1404, 1293, 1541, 1315, 844, 1727, 1569, 1043, 605, 151, 195, 384, 482,
+	618, 430, 1753, 26, 142, 1967, 95, 21, 322, 549, 687, 760, 423, 887, 1245, 1763,
+	266, 348, 610, 1983, 1478, 14, 884, 23, 692, 449, 1010, 1743, 1509, 439, 857, 966,
+	1108, 249, 739, 1304, 1032, 1605, 1556, 1819, 1810, 1915, 1823, 409, 1379, 360, 1904, 900,
+	1127, 570, 1265, 1700, 1685, 495, 1275, 1239, 1739, 688, 1531, 1475, 355, 1496, 584, 1940,
+	252, 263, 429, 873, 392, 196, 137, 127, 90, 340, 169, 297, 298, 585, 111, 325,
+	1109, 1483, 1842, 305, 1645, 1658, 1159, 1279, 1840, 701, 1679, 1198, 1192, 959, 1844, 29,
+	1086, 1060, 31, 311, 1409, 721, 414, 640, 587, 230, 148, 1955, 25, 92, 1748, 1299,
+	749, 254, 1283, 114, 824, 1197, 1383, 1742, 661, 1144, 641, 444, 952, 795, 1972, 706,
+	73, 628, 87, 1941, 636, 1016, 1559, 1712, 1264, 1285, 39, 1774, 324, 702, 422, 532,
+	1400, 1428, 1709, 1232, 1725, 480, 77, 268, 1316, 242, 63, 404, 1153, 410, 709, 260,
+	1493, 1063, 747, 938, 265, 1636, 964, 1875, 470, 2040, 1267, 932, 145, 1805, 100, 488,
+	146, 205, 716, 141, 1771, 256, 293, 1663, 1950, 276, 647, 848, 553, 161, 375, 999,
+	35, 535, 420, 619, 589, 621, 133, 107, 1562, 1539, 40, 343, 1715, 1778, 155, 1135,
+	2043, 684, 761, 520, 1165, 635, 1178, 1888, 946, 1587, 1330, 533, 518, 766, 1892, 926,
+	157, 7, 1745, 1396, 2016, 201, 1022, 193, 956, 1804, 735, 1461, 1321, 1181, 1305, 1006,
+	798, 395, 796, 165, 1327, 1646, 1227, 813, 1397, 868, 1421, 1856, 1948, 309, 793, 1813,
+	2039, 1793, 1216, 279, 1249, 1103, 496, 118, 253, 109, 871, 565, 1084, 15, 543, 1179,
+	479, 220, 424, 361, 3, 27, 563, 695, 1824, 1271, 1187, 1846, 902, 458, 1399, 1718,
+	612, 1442, 1018, 859, 850, 213, 1017, 607, 1095, 1974, 336, 319, 2018, 705, 1120, 1056,
+	1602, 1671, 1050, 1529, 1154, 745, 1928, 284, 1405, 1150, 280, 645, 1859, 1028, 2029, 562,
+	1343, 1647, 390, 1076, 115, 54, 720, 614, 1456, 1230, 11, 116, 436, 613, 741, 505,
+	1370, 1057, 1752, 978, 1464, 906, 1665, 991, 1132, 510, 1226, 1430, 231, 103, 75, 1783,
+	1917, 2041, 1469, 1528, 1733, 469, 1360, 1223, 1408, 406, 1881, 1938, 1900, 1913, 929, 927,
+	1545, 1213, 1161, 778, 2000, 1659, 46, 752, 546, 448, 1779, 957, 1777, 809, 936, 57,
+	106, 1735, 786, 318, 1183, 338, 970, 500, 55, 904, 699, 672, 755, 1359, 578, 1902,
+	71, 391, 1996, 1443, 523, 622, 1440, 742, 86, 837, 1033, 802, 447, 896, 1152, 1251,
+	1837, 552, 664, 226, 471, 1352, 1511, 32, 278, 83, 219, 1326, 1128, 1364, 30, 1093,
+	894, 1277, 486, 922, 1444, 1781, 940, 1843, 2014, 188, 467, 478, 221, 974, 1218, 1290,
+	1307, 1644, 1741, 1792, 1667, 972, 680, 1504, 41, 574, 1494, 1185, 593, 180, 629, 2030,
+	428, 389, 899, 663, 542, 519, 1905, 317, 197, 1651, 1021, 1425, 1291, 538, 124, 38,
+	1384, 248, 1963, 1350, 240, 1051, 788, 498, 1248, 434, 70, 921, 990, 1039, 1005, 1677,
+	474, 1206, 365, 1744, 937, 1351, 167, 1355, 202, 1820, 491, 1142, 854, 1427, 975, 347,
+	243, 1041, 1347, 125, 1073, 1237, 2038, 382, 1626, 1627, 425, 756, 173, 374, 53, 1169,
+	648, 1331, 61, 2002, 97, 1366, 677, 866, 501, 1035, 1926, 140, 250, 1549, 281, 1335,
+	1078, 1488, 452, 711, 1117, 558, 1595, 882, 289, 797, 102, 2045, 1363, 1822, 1515, 1231,
+	1614, 1196, 780, 1516, 1770, 1628, 1958, 1707, 1170, 1506, 1953, 174, 1784, 1965, 1053, 179,
+	1122, 166, 573, 421, 551, 1935, 1966, 881, 1228, 1036, 1932, 913, 1870, 1669, 1592, 912,
+	1381, 1439, 104, 1879, 988, 1606, 1998, 33, 16, 150, 1201, 251, 1538, 1454, 1054, 693,
+	59, 1757, 232, 345, 1234, 312, 1118, 1726, 1699, 1760, 524, 683, 667, 408, 101, 1834,
+	923, 644, 642, 1044, 1519, 1437, 1241, 1952, 1088, 1920, 372, 1789, 1914, 1395, 1684, 1452,
+	1338, 1164, 1716, 750, 787, 385, 239, 461, 1447, 1418, 908, 1702, 1984, 1883, 2004, 1141,
+	1420, 1340, 945, 821, 548, 1607, 1253, 306, 982, 223, 679, 512, 1720, 949, 1918, 1367,
+	183, 1217, 897, 649, 851, 1156, 784, 1302, 771, 1171, 1860, 753, 1722, 1815, 1492, 1959,
+	1348, 1548, 1979, 2006, 1899, 1772, 1530, 1487, 1546, 1001, 1861, 1387, 1077, 601, 1929, 901,
+	853, 257, 1555, 1297, 1734, 1961, 781, 1071, 1534, 1681, 2011, 2012, 418, 300, 302, 825,
+	244, 117, 611, 453, 1601, 1195, 1182, 1828, 314, 1034, 759, 1462, 2025, 353, 697, 138,
+	198, 1906, 191, 1037, 808, 1749, 454, 135, 399, 52, 28, 554, 1137, 1276, 1061, 442,
+	1106, 736, 119, 1714, 1908, 576, 1857, 1686, 1775, 1927, 1886, 637, 1212, 143, 1944, 464,
+	875, 917, 623, 1809, 2, 20, 1851, 829, 1632, 775, 928, 931, 1441, 1578, 1047, 2021,
+	1573, 1143, 1481, 2007, 2036, 608, 308, 388, 1801, 1756, 654, 1124, 176, 34, 168, 6,
+	1324, 581, 1219, 522, 577, 234, 537, 650, 911, 99, 1123, 1732, 534, 547, 733, 1497,
+	950, 1880, 1706, 277, 869, 595, 704, 373, 190, 1376, 1841, 909, 1964, 591, 878, 1523,
+	456, 1257, 1502, 1272, 1576, 1329, 758, 1303, 757, 235, 462, 1704, 1512, 1759, 1769, 560,
+	1473, 1791, 981, 1059, 1429, 160, 1638, 820, 1557, 898, 1641, 1694, 1833, 1375, 1767, 779,
+	977, 1130, 98, 291, 880, 1188, 433, 1019, 1558, 861, 1100, 446, 768, 130, 556, 1160,
+	93, 1575, 1013, 1997, 634, 1386, 299, 200, 1850, 1406, 1887, 962, 2047, 822, 1030, 819,
+	559, 139, 158, 363, 564, 1921, 948, 427, 998, 507, 1146, 968, 1158, 369, 835, 1495,
+	2009, 1438, 1808, 727, 327, 1067, 149, 450, 774, 1358, 60, 153, 531, 1288, 905, 1482,
+	1064, 76, 969, 1891, 210, 1252, 457, 1136, 1424, 1934, 245, 732, 1690, 856, 162, 823,
+	2019, 550, 1776, 288, 267, 979, 356, 1650, 58, 1098, 1309, 568, 1068, 544, 1349, 1336,
+	717, 1070, 1590, 1339, 1260, 1254, 748, 203, 466, 216, 1624, 1925, 1300, 1167, 275, 1649,
+	432, 1214, 499, 1701, 1613, 1333, 1325, 1210, 1991, 400, 726, 1571, 1693, 303, 555, 1780,
+	1446, 707, 1292, 769, 1985, 1799, 1313, 586, 437, 2035, 1378, 1949, 359, 557, 996, 1971,
+	983, 1796, 1524, 1266, 484, 1460, 1885, 1689, 1656, 815, 626, 1755, 1874, 817, 1385, 1975,
+	1431, 1403, 1020, 1114, 1754, 1026, 751, 986, 1046, 286, 1982, 1391, 1507, 1640, 1190, 481,
+	1072, 1432, 1639, 1705, 1423, 1157, 1320, 12, 740, 1567, 1995, 1259, 1806, 1890, 170, 1402,
+	1848, 113, 1202, 864, 841, 487, 1903, 1812, 1993, 1458, 1603, 1024, 272, 832, 947, 743,
+	1898, 525, 1574, 1258, 973, 1564, 1617, 285, 816, 1099, 1453, 1115, 506, 1262, 468, 1208,
+	1579, 1388, 915, 1662, 1318, 84, 1615, 207, 1491, 1847, 599, 472, 1611, 1717, 1786, 1525,
+	1829, 1412, 668, 804, 1080, 2034, 441, 953, 460, 1620, 1184, 1471, 1373, 843, 85, 529,
+	81, 1365, 1362, 1240, 673, 1570, 1049, 1544, 1585, 1308, 754, 1800, 24, 48, 22, 596,
+	110, 762, 1472, 1177, 371, 652, 1633, 1295, 1296, 698, 1750, 627, 872, 94, 1654, 2022,
+	1692, 1357, 2015, 1782, 503, 1811, 2028, 1129, 1990, 1852, 1543, 1281, 37, 313, 270, 0,
+	1604, 886, 729, 2020, 1675, 1946, 686, 1207, 1795, 933, 662, 1140, 1002, 1951, 1273, 328,
+	184, 126, 827, 413, 1691, 144, 323, 459, 387, 1211, 1310, 907, 1973, 833, 224, 1505,
+	728, 2046, 366, 1653, 1536, 1986, 370, 540, 1696, 811, 1081, 1138, 1703, 1500, 1451, 1029,
+	344, 1455, 883, 1565, 1134, 1322, 690, 1485, 403, 333, 121, 885, 1622, 516, 1501, 1510,
+	1205, 2032, 876, 1853, 1390, 1243, 1015, 903, 346, 1623, 1301, 1566, 1112, 1580, 1635, 722,
+	1468, 994, 187, 1334, 49, 435, 1261, 1657, 1839, 332, 1695, 776, 377, 354, 858, 694,
+	1353, 1970, 1374, 177, 657, 1023, 575, 1311, 655, 1593, 1377, 1014, 154, 616, 68, 1683,
+	1215, 860, 646, 383, 206, 2003, 1174, 350, 1089, 1052, 1643, 658, 1583, 1532, 1678, 1600,
+	1817, 352, 696, 830, 1126, 1596, 438, 1589, 1011, 1994, 807, 171, 1609, 685, 417, 489,
+	1393, 1040, 1009, 1233, 789, 218, 1180, 407, 1204, 1111, 1535, 1107, 490, 1845, 963, 730,
+	227, 163, 703, 1832, 261, 1729, 222, 708, 62, 1163, 930, 443, 1354, 1246, 1827, 572,
+	1598, 136, 1372, 914, 1209, 1807, 592, 485, 1407, 865, 1250, 199, 1323, 1083, 380, 1319,
+	1380, 1672, 1933, 603, 579, 1125, 1957, 1490, 791, 1831, 1977, 515, 465, 1401, 1637, 1723,
+	1394, 714, 1668, 304, 364, 122, 1873, 415, 891, 1826, 1943, 211, 643, 175, 51, 951,
+	1186, 1075, 1236, 1514, 731, 1155, 294, 335, 624, 492, 1489, 892, 1803, 1882, 1306, 812,
+	1599, 1270, 473, 874, 1980, 925, 960, 1553, 1923, 43, 1113, 1660, 1867, 1242, 810, 1787,
+	1225, 1765, 1027, 1371, 870, 2005, 1916, 1274, 1368, 939, 1835, 1581, 1547, 867, 1969, 1797,
+	1238, 942, 79, 329, 594, 131, 604, 528, 767, 1317, 1866, 1247, 1433, 987, 419, 617,
+	2042, 1568, 681, 1449, 262, 1661, 316, 1269, 1457, 971, 746, 719, 1435, 530, 602, 805,
+	665, 1520, 1048, 1220, 1816, 1298, 1008, 765, 342, 1989, 2024, 326, 536, 566, 67, 1922,
+	1910, 1268, 967, 1981, 1740, 1012, 2010, 2017, 1728, 1976, 1836, 1894, 1038, 1244, 1747, 1337,
+	1222, 517, 836, 504, 852, 1467, 258, 1346, 440, 1764, 855, 1664, 1550, 1674, 1477, 1025,
+	1551, 818, 56, 290, 1221, 738, 666, 934, 895, 128, 1794, 700, 1422, 1398, 247, 1003,
+	924, 2001, 889, 381, 74, 1682, 246, 18, 1768, 228, 271, 569, 451, 625, 1930, 378,
+	233, 164, 916, 561, 1176, 229, 1498, 828, 800, 367, 1436, 1055, 204, 362, 710, 890,
+	1148, 582, 920, 351, 493, 1582, 296, 783, 1499, 1942, 799, 47, 862, 958, 1939, 483,
+	514, 675, 236, 1788, 1954, 1426, 1937, 1102, 826, 508, 1131, 301, 476, 1586, 1162, 1648,
+	334, 588, 842, 159, 678, 1172, 1116, 849, 192, 1710, 1105, 1625, 1450, 600, 620, 1280,
+	1345, 770, 1931, 1145, 656, 1389, 839, 877, 1655, 571, 1522, 1224, 1255, 214, 1588, 1618,
+	1919, 1074, 1751, 152, 1798, 1133, 997, 1173, 1459, 1486, 331, 1901, 82, 676, 379, 989,
+	954, 1518, 1630, 590, 651, 737, 630, 1062, 1416, 217, 955, 1584, 893, 1445, 320, 1945,
+	321, 1278, 1294, 172, 1263, 682, 225, 691, 1680, 580, 1527, 1591, 1719, 1634, 539, 1736,
+	609, 2026, 307, 1849, 1342, 411, 2008, 1724, 1235, 238, 744, 1097, 402, 1762, 847, 659,
+	1577, 1094, 1069, 615, 1286, 1619, 1058, 724, 995, 9, 181, 315, 269, 398, 1830, 405,
+	66, 1698, 1992, 1065, 763, 237, 1031, 45, 156, 330, 1369, 1537, 545, 477, 1168, 1652,
+	1474, 1897, 445, 1121, 1711, 1101, 1862, 1865, 108, 215, 2037, 120, 1382, 1988, 1889, 69,
+	1289, 1896, 89, 941, 1561, 1517, 1552, 509, 785, 1871, 1864, 1673, 1868, 1818, 426, 1909,
+	1610, 790, 2013, 1147, 134, 1104, 88, 283, 1814, 794, 1392, 1758, 1907, 1687, 1465, 2027,
+	1960, 782, 1079, 1480, 919, 1413, 1344, 1513, 653, 1007, 1821, 1854, 660, 1855, 1676, 718,
+	1194, 1858, 1191, 1612, 984, 1419, 1341, 840, 494, 1312, 1434, 1284, 209, 264, 1256, 1189,
+	105, 1869, 129, 2023, 1314, 1802, 1526, 132, 1878, 527, 773, 671, 803, 597, 670, 1737,
+	455, 1090, 431, 1877, 1091, 712, 1621, 1540, 1328, 943, 638, 1476, 259, 287, 1175, 1594,
+	394, 1287, 502, 1004, 1484, 1356, 1096, 1895, 358, 1738, 631, 674, 1912, 632, 935, 341,
+	401, 792, 1085, 734, 976, 918, 72, 368, 1361, 1978, 1987, 1730, 980, 1616, 993, 412,
+	888, 396, 725, 50, 194, 1766, 1554, 2044, 1697, 386, 416, 1503, 689, 526, 863, 393,
+	772, 1872, 1151, 1962, 273, 212, 723, 1463, 185, 961, 1332, 801, 339, 944, 513, 310,
+	1884, 633, 583, 985, 1721, 1042, 1410, 1893, 1911, 463, 2031, 606, 1110, 1542, 1708, 497,
+	845, 349, 1936, 1999, 1193, 1956, 1746, 1947, 1773, 1521, 1642, 397, 1119, 1838, 1139, 91,
+	78, 337, 1533, 1414, 1479, 567, 838, 806, 1688, 1631, 1666, 1629, 1713, 1199, 715, 1415,
+	1045, 1203, 147, 511, 1282, 598, 1417, 96, 186, 1790, 1000, 1825, 965, 1200, 1508, 814,
+];
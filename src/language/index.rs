@@ -0,0 +1,59 @@
+/*
+    Reverse lookup (word -> index) for the predefined wordlists.
+
+    Every language module has a SORTED_INDEXES table next to its WORDS: the indexes of WORDS ordered by the bytes
+    of the word they point to. The tables are precomputed and checked in like the wordlists themselves,
+    so a word is found with a binary search (11 comparisons) instead of a linear scan over 2048 words.
+    The wordlists are stored in NFKD, so the byte order of the words is stable.
+    After editing a wordlist, regenerate its table by sorting 0..2048 by the bytes of WORDS[i] (16 indexes per line),
+    the test below fails until the table matches the wordlist again.
+*/
+
+use core::ops::Range;
+
+const WORDS_COUNT: usize = 2048;
+
+/// Binary search of `word` in `words` using the permutation in `sorted_indexes`
+pub fn find_word_index(words: &[&str; WORDS_COUNT], sorted_indexes: &[u16; WORDS_COUNT], word: &str) -> Option<u16> {
+    sorted_indexes
+        .binary_search_by(|index| words[*index as usize].as_bytes().cmp(word.as_bytes()))
+        .ok()
+        .map(|position| sorted_indexes[position])
+}
+
+/// Positions in `sorted_indexes` of every word starting with `prefix`, words sharing a prefix are contiguous
+pub fn find_prefix_range(words: &[&str; WORDS_COUNT], sorted_indexes: &[u16; WORDS_COUNT], prefix: &str) -> Range<usize> {
+    let word = |index: &u16| words[*index as usize].as_bytes();
+
+    let start = sorted_indexes.partition_point(|index| word(index) < prefix.as_bytes());
+    let end = sorted_indexes.partition_point(|index| word(index) < prefix.as_bytes() || word(index).starts_with(prefix.as_bytes()));
+
+    start..end
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+
+    #[test]
+    fn test_sorted_indexes_are_sorted_permutations() {
+        for language in Language::ALL {
+            let words = Language::get_predefined_word_list(&language);
+            let sorted_indexes = Language::get_sorted_indexes(&language);
+
+            let mut seen = [false; WORDS_COUNT];
+            for index in sorted_indexes {
+                assert!(!seen[*index as usize], "{:?} index {} appears twice", language, index);
+                seen[*index as usize] = true;
+            }
+
+            // Strictly increasing, so the words are also unique
+            for pair in sorted_indexes.windows(2) {
+                let (previous, next) = (words[pair[0] as usize], words[pair[1] as usize]);
+                assert!(previous.as_bytes() < next.as_bytes(), "{:?} {} is sorted before {}", language, previous, next);
+            }
+        }
+    }
+}
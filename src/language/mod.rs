@@ -8,8 +8,14 @@ mod japanese;
 mod korean;
 mod portuguese;
 mod spanish;
+mod index;
 
+use crate::{utils, Mnemonic, MnemonicError, WordList};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use unicode_normalization::{is_nfkd_quick, IsNormalized};
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Language {
     ChineseSimplified,
     ChineseTraditional,
@@ -24,18 +30,201 @@ pub enum Language {
 }
 
 impl Language {
-    pub const fn get_predefined_word_list(language: &Language) -> [&'static str; 2048] {
+    /// Every supported language, in the same order as the enum
+    pub const ALL: [Language; 10] = [
+        Language::ChineseSimplified,
+        Language::ChineseTraditional,
+        Language::Czech,
+        Language::English,
+        Language::French,
+        Language::Italian,
+        Language::Japanese,
+        Language::Korean,
+        Language::Portuguese,
+        Language::Spanish,
+    ];
+
+    pub const fn get_predefined_word_list(language: &Language) -> &'static [&'static str; 2048] {
+        match language {
+            Language::ChineseSimplified => &chinese_simplified::WORDS,
+            Language::ChineseTraditional => &chinese_traditional::WORDS,
+            Language::Czech => &czech::WORDS,
+            Language::English => &english::WORDS,
+            Language::French => &french::WORDS,
+            Language::Italian => &italian::WORDS,
+            Language::Japanese => &japanese::WORDS,
+            Language::Portuguese => &portuguese::WORDS,
+            Language::Spanish => &spanish::WORDS,
+            Language::Korean => &korean::WORDS,
+        }
+    }
+
+    const fn get_sorted_indexes(language: &Language) -> &'static [u16; 2048] {
         match language {
-            Language::ChineseSimplified => chinese_simplified::WORDS,
-            Language::ChineseTraditional => chinese_traditional::WORDS,
-            Language::Czech => czech::WORDS,
-            Language::English => english::WORDS,
-            Language::French => french::WORDS,
-            Language::Italian => italian::WORDS,
-            Language::Japanese => japanese::WORDS,
-            Language::Portuguese => portuguese::WORDS,
-            Language::Spanish => spanish::WORDS,
-            Language::Korean => korean::WORDS,
-        }
-    }
-}
\ No newline at end of file
+            Language::ChineseSimplified => &chinese_simplified::SORTED_INDEXES,
+            Language::ChineseTraditional => &chinese_traditional::SORTED_INDEXES,
+            Language::Czech => &czech::SORTED_INDEXES,
+            Language::English => &english::SORTED_INDEXES,
+            Language::French => &french::SORTED_INDEXES,
+            Language::Italian => &italian::SORTED_INDEXES,
+            Language::Japanese => &japanese::SORTED_INDEXES,
+            Language::Portuguese => &portuguese::SORTED_INDEXES,
+            Language::Spanish => &spanish::SORTED_INDEXES,
+            Language::Korean => &korean::SORTED_INDEXES,
+        }
+    }
+
+    /// ISO 639-1 code of the language, the Chinese wordlists add the script subtag ("zh-Hans", "zh-Hant")
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Language::ChineseSimplified => "zh-Hans",
+            Language::ChineseTraditional => "zh-Hant",
+            Language::Czech => "cs",
+            Language::English => "en",
+            Language::French => "fr",
+            Language::Italian => "it",
+            Language::Japanese => "ja",
+            Language::Korean => "ko",
+            Language::Portuguese => "pt",
+            Language::Spanish => "es",
+        }
+    }
+
+    /// Resolves a language from its code as returned by `code`, ignoring case
+    pub fn from_code(code: &str) -> Option<Language> {
+        Language::ALL.into_iter().find(|language| language.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Returns the index (0 to 2047) of a word in the wordlist, or None if the word is not in it
+    ///
+    /// Uses a binary search over the precomputed SORTED_INDEXES table of the wordlist, the word is NFKD normalized if needed.
+    pub fn index_of(&self, word: &str) -> Option<u16> {
+        let words = Self::get_predefined_word_list(self);
+        let sorted_indexes = Self::get_sorted_indexes(self);
+
+        match is_nfkd_quick(word.chars()) {
+            IsNormalized::Yes => index::find_word_index(words, sorted_indexes, word),
+            _ => index::find_word_index(words, sorted_indexes, &utils::normalize(word)),
+        }
+    }
+
+    /// Returns every word of the wordlist starting with `prefix`, in byte order of the words
+    ///
+    /// Meant for autocompletion in UIs, the prefix is NFKD normalized if needed.
+    pub fn complete(&self, prefix: &str) -> impl Iterator<Item = &'static str> {
+        let words = Self::get_predefined_word_list(self);
+        let sorted_indexes = Self::get_sorted_indexes(self);
+
+        let range = match is_nfkd_quick(prefix.chars()) {
+            IsNormalized::Yes => index::find_prefix_range(words, sorted_indexes, prefix),
+            _ => index::find_prefix_range(words, sorted_indexes, &utils::normalize(prefix)),
+        };
+
+        sorted_indexes[range].iter().map(|index| words[*index as usize])
+    }
+
+    /// Resolves a word or an unambiguous prefix of a word (e.g. the first four letters) to the full word
+    ///
+    /// An exact match always wins, so "act" resolves to "act" even though "action" starts with it.
+    /// Returns None if no word or more than one word starts with `prefix`.
+    pub fn expand_prefix(&self, prefix: &str) -> Option<&'static str> {
+        WordList::expand_prefix_index(self, prefix).and_then(|index| self.word_at(index))
+    }
+
+    /// Returns the word at `index` in the wordlist, or None if the index is out of the 11-bit range
+    pub fn word_at(&self, index: u16) -> Option<&'static str> {
+        Self::get_predefined_word_list(self).get(index as usize).copied()
+    }
+
+    /// Detects the language of a mnemonic phrase
+    ///
+    /// A language is a candidate only if every word is in its wordlist and the checksum passes.
+    /// Words must be complete, abbreviations are not expanded like `Mnemonic::from_phrase` does.
+    /// Returns `MnemonicError::AmbiguousLanguage` with all candidates if more than one language matches
+    /// (e.g. phrases built only from characters shared by ChineseSimplified and ChineseTraditional).
+    pub fn detect(phrase: &str) -> Result<Language, MnemonicError> {
+        let mut candidates = Vec::new();
+        let mut closest_error: Option<MnemonicError> = None;
+
+        for language in Language::ALL {
+            match Mnemonic::from_exact_phrase(phrase, language) {
+                Ok(_) => candidates.push(language),
+                Err(e) => {
+                    // When nothing matches I report the error of the language that got furthest in the phrase
+                    if closest_error.as_ref().is_none_or(|closest| Self::error_rank(&e) > Self::error_rank(closest)) {
+                        closest_error = Some(e);
+                    }
+                }
+            }
+        }
+
+        match candidates.len() {
+            0 => Err(closest_error.expect("At least one language is always checked")),
+            1 => Ok(candidates[0]),
+            _ => Err(MnemonicError::AmbiguousLanguage(candidates)),
+        }
+    }
+
+    fn error_rank(error: &MnemonicError) -> usize {
+        match error {
+            MnemonicError::InvalidChecksum { .. } => usize::MAX, // every word was found, only the checksum failed
+            MnemonicError::UnknownWord { index, .. } | MnemonicError::AmbiguousWord { index, .. } => *index,
+            _ => 0,
+        }
+    }
+}
+
+impl WordList for Language {
+    fn word_at(&self, index: u16) -> Option<&str> {
+        Language::word_at(self, index)
+    }
+
+    fn index_of(&self, word: &str) -> Option<u16> {
+        Language::index_of(self, word)
+    }
+
+    fn complete<'a>(&'a self, prefix: &str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        Box::new(Language::complete(self, prefix).map(|word| -> &'a str { word }))
+    }
+
+    /// Japanese phrases are displayed with the ideographic space, it is NFKD normalized to a regular space
+    fn separator(&self) -> &str {
+        match self {
+            Language::Japanese => "\u{3000}",
+            _ => " ",
+        }
+    }
+
+    fn language(&self) -> Option<Language> {
+        Some(*self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Language {
+    /// Serialized as its language code, e.g. `"en"`
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Language {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Language, D::Error> {
+        struct CodeVisitor;
+
+        impl serde::de::Visitor<'_> for CodeVisitor {
+            type Value = Language;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "a language code such as \"en\" or \"zh-Hans\"")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, code: &str) -> Result<Language, E> {
+                Language::from_code(code).ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(code), &self))
+            }
+        }
+
+        deserializer.deserialize_str(CodeVisitor)
+    }
+}
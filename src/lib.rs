@@ -1,32 +1,91 @@
-use rand::rngs::OsRng;
-use rand::RngCore;
-use sha256::digest;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use pbkdf2::pbkdf2_hmac;
+#[cfg(feature = "std")]
+use rand::rngs::OsRng;
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::{Zeroize, Zeroizing};
+
+pub mod bip32;
+#[cfg(feature = "bls")]
+pub mod bls;
+mod encoding;
+#[cfg(feature = "ethereum")]
+pub mod ethereum;
+mod indices;
 mod language;
+#[cfg(feature = "ed25519")]
+pub mod slip10;
 mod types;
 mod utils;
+mod wordlist;
 
+pub use indices::MnemonicIndices;
 pub use language::Language;
 pub use crate::types::MnemonicType;
+pub use crate::wordlist::{CustomWordList, WordList, WordListError};
+#[allow(deprecated)]
 pub use utils::hex_to_binary;
 
-const MIN_WORDS: usize = 12;
-const MAX_WORDS: usize = 24;
-const DEFAULT_MNEMONIC_TYPE: MnemonicType = MnemonicType::Bits256; // Default Mnemonic Type when error occurs
+const PBKDF2_ROUNDS: u32 = 2048; // Iterations of PBKDF2 required by BIP-39 for seed derivation
 
 #[derive(Debug)]
 pub enum MnemonicError {
-    InvalidChecksum,
-    InvalidEntropy,
-    GeneratorError,
+    /// Checksum encoded in the phrase doesn't match the one computed from its entropy
+    InvalidChecksum { expected: u8, found: u8 },
+    /// Entropy length in bytes is not one of 16, 20, 24, 28 or 32
+    BadEntropyLength(usize),
+    /// Random number generator failed to provide entropy
+    GeneratorError(rand_core::Error),
+    /// Word at `index` (counted from 0) is not in the wordlist
+    UnknownWord { index: usize, word: String },
+    /// Word at `index` (counted from 0) is an abbreviation matching more than one word of the wordlist
+    AmbiguousWord { index: usize, word: String },
+    /// Number of words is not one of 12, 15, 18, 21 or 24
+    BadWordCount(usize),
+    /// Phrase is valid in more than one language
+    AmbiguousLanguage(Vec<Language>),
+}
+
+impl core::fmt::Display for MnemonicError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            MnemonicError::InvalidChecksum { expected, found } => {
+                write!(f, "Invalid checksum: expected {:#04x}, found {:#04x}.", expected, found)
+            }
+            MnemonicError::BadEntropyLength(bytes) => {
+                write!(f, "Invalid entropy length: expected 16, 20, 24, 28 or 32 bytes, but got {} bytes.", bytes)
+            }
+            MnemonicError::GeneratorError(e) => write!(f, "Error when generating entropy: {}.", e),
+            MnemonicError::UnknownWord { index, word } => {
+                write!(f, "Unknown word \"{}\" at position {} of the mnemonic phrase.", word, index)
+            }
+            MnemonicError::AmbiguousWord { index, word } => {
+                write!(f, "Abbreviated word \"{}\" at position {} matches more than one word.", word, index)
+            }
+            MnemonicError::BadWordCount(count) => {
+                write!(f, "Invalid number of words: expected 12, 15, 18, 21 or 24, but got {}.", count)
+            }
+            MnemonicError::AmbiguousLanguage(languages) => {
+                write!(f, "Mnemonic phrase is valid in more than one language: {:?}.", languages)
+            }
+        }
+    }
 }
 
-impl std::fmt::Display for MnemonicError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+#[cfg(feature = "std")]
+impl std::error::Error for MnemonicError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            MnemonicError::InvalidChecksum => write!(f, "Invalid checksum."),
-            MnemonicError::InvalidEntropy => write!(f, "Invalid entropy."),
-            MnemonicError::GeneratorError => write!(f, "Error when creating Mnemonic instance!")
+            MnemonicError::GeneratorError(e) => Some(e),
+            _ => None,
         }
     }
 }
@@ -36,14 +95,38 @@ pub struct EntropyInfo {
     pub bits: usize,
 }
 
+/// Seeds of the same entropy encoded with two wordlists, returned by `Mnemonic::compare_seeds`
+///
+/// Both seeds are wiped from memory when the value is dropped
+pub struct SeedComparison {
+    pub original: Zeroizing<[u8; 64]>,
+    pub translated: Zeroizing<[u8; 64]>,
+}
+
+impl SeedComparison {
+    /// True only if both wordlists produce the same words, e.g. when translating to the same language
+    pub fn is_same(&self) -> bool {
+        self.original == self.translated
+    }
+}
+
 pub struct Mnemonic {
-    lang: Language,
+    wordlist: Arc<dyn WordList>,
     mnemonic_type: MnemonicType,
     entropy: Vec<u8>,
     checksum: u8,
     mnemonic_phrase: Vec<String>,
 }
 
+impl Drop for Mnemonic {
+    /// Wipes entropy, checksum and every word of the phrase before the memory is freed
+    fn drop(&mut self) {
+        self.entropy.zeroize();
+        self.checksum.zeroize();
+        self.mnemonic_phrase.zeroize();
+    }
+}
+
 
 impl Mnemonic {
 
@@ -53,16 +136,15 @@ impl Mnemonic {
             return false;
         }
 
-        // Check if mnemonic_phrase words count is in the required Ranges
+        // Check if mnemonic_phrase words count matches one of the supported mnemonic types
         let word_count = self.mnemonic_phrase.len();
-        if !matches!(word_count, MIN_WORDS | MAX_WORDS) {
+        if MnemonicType::from_words_count(word_count).is_none() {
             return false;
         }
 
         // Check if phrases exists in the wordlist
-        let wordlist = Language::get_predefined_word_list(&self.lang);
         for word in &self.mnemonic_phrase {
-            if !wordlist.contains(&word.as_str()) {
+            if self.wordlist.index_of(word).is_none() {
                 return false;
             }
         }
@@ -70,85 +152,165 @@ impl Mnemonic {
         true
     }
 
-    /// Wrapper for .generator() function, created to handle errors
-    pub fn new(lang: Language, mnemonic_type: MnemonicType) -> Mnemonic {
-        match Self::generator(lang, mnemonic_type) {
-            Ok(mut mnemonic) => {
-                let binary_entropy = Self::convert_entropy_to_binary(&mnemonic.entropy);
-                mnemonic.mnemonic_phrase_generation(binary_entropy);
+    /// Wrapper for .try_new() function
+    ///
+    /// Panics if the mnemonic can't be generated, use `try_new` to handle the error instead
+    #[cfg(feature = "std")]
+    pub fn new<W: WordList + 'static>(lang: W, mnemonic_type: MnemonicType) -> Mnemonic {
+        Self::try_new(lang, mnemonic_type).expect("Error creating mnemonic")
+    }
 
-                // Check if the generated mnemonic is valid before returning it
-                if mnemonic.is_valid() {
-                    mnemonic
-                } else {
-                    eprintln!("Generated mnemonic is invalid, using default fallback.");
-                    Mnemonic::default()
-                }
-            }
-            Err(e) => {
-                eprintln!("Error creating mnemonic: {}, using default fallback", e);
-                // Provide default Mnemonic as fallback if error happen
-                Mnemonic::default()
-            }
-        }
+    /// Generates a new random mnemonic, errors are returned to the caller and never replaced by another mnemonic
+    ///
+    /// Entropy comes from the operating system RNG, use `generate_with_rng` to provide another source
+    #[cfg(feature = "std")]
+    pub fn try_new<W: WordList + 'static>(lang: W, mnemonic_type: MnemonicType) -> Result<Mnemonic, MnemonicError> {
+        Self::generate_with_rng(&mut OsRng, lang, mnemonic_type)
+    }
+
+    /// Generates a new mnemonic with entropy drawn from the given cryptographically secure RNG
+    ///
+    /// Useful to inject a seeded RNG (e.g. ChaCha) in deterministic tests or a hardware RNG adapter in production
+    pub fn generate_with_rng<R: RngCore + CryptoRng, W: WordList + 'static>(
+        rng: &mut R,
+        lang: W,
+        mnemonic_type: MnemonicType,
+    ) -> Result<Mnemonic, MnemonicError> {
+        let mut mnemonic = Self::generator(rng, lang, mnemonic_type)?;
+
+        mnemonic.mnemonic_phrase_generation();
+
+        // A phrase generated from valid entropy is always valid
+        debug_assert!(mnemonic.is_valid());
+
+        Ok(mnemonic)
     }
 
     /// Main function for creating an instance of Mnemonic Struct
-    fn generator(lang: Language, mnemonic_type: MnemonicType) -> Result<Mnemonic, MnemonicError> {
+    fn generator<R: RngCore + CryptoRng, W: WordList + 'static>(
+        rng: &mut R,
+        lang: W,
+        mnemonic_type: MnemonicType,
+    ) -> Result<Mnemonic, MnemonicError> {
         /*
         This is responsible to create Mnemonic instance and set initial values for entropy and checksum
         */
-        let (mut raw_entropy, checksum_decimal) = utils::prepare_data_for_mnemonic_struct_initialization(mnemonic_type); // Derive entropy and checksum
+        let (mut raw_entropy, checksum_decimal) = utils::prepare_data_for_mnemonic_struct_initialization(rng, mnemonic_type)?; // Derive entropy and checksum
 
-        Self::validate_entropy(&raw_entropy).map_err(|_e| MnemonicError::InvalidEntropy)?;
+        Self::validate_entropy(&raw_entropy)?;
 
         raw_entropy.push(checksum_decimal);
 
         Ok(Mnemonic {
-            lang,
+            wordlist: Arc::new(lang),
             mnemonic_type,
             entropy: raw_entropy, // I store it entropy + checksum
             checksum: checksum_decimal,
-            mnemonic_phrase: Vec::new(),
+            mnemonic_phrase: Vec::with_capacity(mnemonic_type.words_count()),
         })
     }
 
-    /// Handler if an error occurs inside .new() wrapper of .generator() to return default Mnemonic instance
-    fn default() -> Mnemonic {
-            let (mut raw_entropy, checksum_decimal) = utils::prepare_data_for_mnemonic_struct_initialization(DEFAULT_MNEMONIC_TYPE);
-            raw_entropy.push(checksum_decimal);
+    /// Creates a mnemonic from caller-supplied entropy bytes instead of generating them
+    pub fn from_entropy<W: WordList + 'static>(entropy: &[u8], lang: W) -> Result<Mnemonic, MnemonicError> {
+        Self::validate_entropy(entropy)?;
 
-            let mut mnemonic = Mnemonic {
-                lang: Language::English,
-                mnemonic_type: DEFAULT_MNEMONIC_TYPE,
-                entropy: raw_entropy,
-                checksum: checksum_decimal,
-                mnemonic_phrase: Vec::new(),
-            };
+        let mnemonic_type = MnemonicType::from_bytes_count(entropy.len())
+            .ok_or(MnemonicError::BadEntropyLength(entropy.len()))?;
+        let checksum_decimal = Self::generate_checksum(entropy, mnemonic_type);
+
+        // Room for the checksum byte, so pushing it doesn't reallocate and leave a copy of the entropy behind
+        let mut raw_entropy = Vec::with_capacity(entropy.len() + 1);
+        raw_entropy.extend_from_slice(entropy);
+        raw_entropy.push(checksum_decimal);
+
+        let mut mnemonic = Mnemonic {
+            wordlist: Arc::new(lang),
+            mnemonic_type,
+            entropy: raw_entropy,
+            checksum: checksum_decimal,
+            mnemonic_phrase: Vec::with_capacity(mnemonic_type.words_count()),
+        };
 
-            let binary_entropy = Self::convert_entropy_to_binary(&mnemonic.entropy);
-            mnemonic.mnemonic_phrase_generation(binary_entropy);
-            mnemonic
+        mnemonic.mnemonic_phrase_generation();
+        Ok(mnemonic)
     }
 
-    pub fn validate_checksum(&self) -> Result<bool, MnemonicError> {
+    /// Parses an existing mnemonic phrase and rebuilds its entropy and checksum
+    ///
+    /// Words can be abbreviated (e.g. the first four letters stamped on a metal backup)
+    /// as long as every abbreviation matches a single word, the full words are stored in the mnemonic.
+    pub fn from_phrase<W: WordList + 'static>(phrase: &str, lang: W) -> Result<Mnemonic, MnemonicError> {
+        Self::parse_phrase(phrase, lang, true)
+    }
+
+    /// Parses a phrase of full words only, abbreviations are `UnknownWord` errors
+    ///
+    /// Used where the language is guessed: a full-word phrase of one language could otherwise pass as
+    /// abbreviations of another one.
+    pub(crate) fn from_exact_phrase<W: WordList + 'static>(phrase: &str, lang: W) -> Result<Mnemonic, MnemonicError> {
+        Self::parse_phrase(phrase, lang, false)
+    }
+
+    fn parse_phrase<W: WordList + 'static>(
+        phrase: &str,
+        lang: W,
+        abbreviations: bool,
+    ) -> Result<Mnemonic, MnemonicError> {
         /*
-            I use binary representation of entropy since i store entropy + checksum
-            Then i calculate how many bits is my checksum and i retrieve it
-            I convert it to decimal and compare it with my self.checksum to see if it is the same
+            The phrase is normalized by the wordlist first (NFKD, the predefined wordlists are stored in NFKD as well).
+            The checksum is recomputed from the entropy and must match the one encoded in the phrase.
         */
-        let binary_entropy = Self::convert_entropy_to_binary(&self.entropy);
-        let checksum_bits = self.mnemonic_type.bits() / 32;
+        let phrase = lang.normalize(phrase);
+        let words: Vec<&str> = phrase.split_whitespace().collect();
+        let (mnemonic_type, mut raw_entropy, checksum_decimal) = Self::decode_words(&words, &lang, abbreviations)?;
 
-        if binary_entropy.len() < checksum_bits {
-            return Err(MnemonicError::InvalidChecksum);
+        let expected_checksum = Self::generate_checksum(&raw_entropy, mnemonic_type);
+        if checksum_decimal != expected_checksum {
+            return Err(MnemonicError::InvalidChecksum { expected: expected_checksum, found: checksum_decimal });
         }
 
-        let checksum_binary = &binary_entropy[binary_entropy.len() - checksum_bits..];
-        let checksum_decimal = u8::from_str_radix(checksum_binary, 2)
-            .map_err(|_| MnemonicError::InvalidChecksum)?;
+        raw_entropy.push(checksum_decimal);
 
-        Ok(checksum_decimal == self.checksum)
+        let mut mnemonic = Mnemonic {
+            wordlist: Arc::new(lang),
+            mnemonic_type,
+            entropy: core::mem::take(&mut *raw_entropy),
+            checksum: checksum_decimal,
+            mnemonic_phrase: Vec::with_capacity(mnemonic_type.words_count()),
+        };
+
+        // Words are generated again from the entropy, so abbreviations are stored as full words
+        mnemonic.mnemonic_phrase_generation();
+        Ok(mnemonic)
+    }
+
+    /// Recomputes the checksum from the words of the phrase and compares it with the one encoded in the last word
+    pub fn validate_checksum(&self) -> Result<bool, MnemonicError> {
+        /*
+            Entropy and checksum are decoded again from self.mnemonic_phrase, the stored fields are not trusted
+            SHA-256 is recomputed over the decoded entropy, so a tampered or mistyped word is detected
+        */
+        let words: Vec<&str> = self.mnemonic_phrase.iter().map(String::as_str).collect();
+        let (mnemonic_type, raw_entropy, checksum_decimal) = Self::decode_words(&words, &*self.wordlist, false)?;
+
+        Ok(checksum_decimal == Self::generate_checksum(&raw_entropy, mnemonic_type))
+    }
+
+    /// Re-encodes the same entropy with the words of another language
+    ///
+    /// Entropy and checksum are kept, but the seed returned by `to_seed` is different:
+    /// BIP-39 hashes the words of the phrase, not the entropy, so a translated phrase restores a different wallet.
+    /// Use `compare_seeds` to see both seeds side by side.
+    pub fn to_language<W: WordList + 'static>(&self, lang: W) -> Mnemonic {
+        Self::from_entropy(self.entropy(), lang).expect("Entropy of a mnemonic is always valid")
+    }
+
+    /// Derives the seed of this mnemonic and of its translation to `lang` with the same passphrase
+    pub fn compare_seeds<W: WordList + 'static>(&self, lang: W, passphrase: &str) -> SeedComparison {
+        SeedComparison {
+            original: self.to_seed(passphrase),
+            translated: self.to_language(lang).to_seed(passphrase),
+        }
     }
 
     /// Getter for the mnemonic phrase.
@@ -156,63 +318,118 @@ impl Mnemonic {
         &self.mnemonic_phrase
     }
 
+    /// Mnemonic phrase as a single string, words are joined with the separator of the wordlist
+    pub fn phrase(&self) -> Zeroizing<String> {
+        Zeroizing::new(self.mnemonic_phrase.join(self.wordlist.separator()))
+    }
+
+    /// Wordlist used to encode the mnemonic
+    pub fn wordlist(&self) -> &dyn WordList {
+        &*self.wordlist
+    }
+
+    /// Derives the 64-byte BIP-39 seed with PBKDF2-HMAC-SHA512, the passphrase can be empty
+    ///
+    /// The seed is wiped from memory when the returned value is dropped
+    pub fn to_seed(&self, passphrase: &str) -> Zeroizing<[u8; 64]> {
+        /*
+            Password is the mnemonic phrase joined with spaces
+            Salt is "mnemonic" + passphrase, hashed 2048 times
+            Both are NFKD normalized before hashing
+        */
+        let phrase = utils::normalize(&Zeroizing::new(self.mnemonic_phrase.join(" ")));
+        let passphrase = utils::normalize(passphrase);
+
+        let mut salt = Zeroizing::new(String::with_capacity("mnemonic".len() + passphrase.len()));
+        salt.push_str("mnemonic");
+        salt.push_str(&passphrase);
+
+        let mut seed = Zeroizing::new([0u8; 64]);
+        pbkdf2_hmac::<Sha512>(phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut *seed);
+        seed
+    }
+
+    /// Getter for the raw entropy, without the checksum byte.
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy[..self.mnemonic_type.bytes()]
+    }
+
     /// Bellow are functions that implement my bip39 cryptography
-    fn generate_entropy(mnemonic_type: MnemonicType) -> Vec<u8> {
-        let mut rng = OsRng {};
+    fn generate_entropy<R: RngCore + CryptoRng>(rng: &mut R, mnemonic_type: MnemonicType) -> Result<Vec<u8>, MnemonicError> {
         let entropy_bytes_count = mnemonic_type.bytes();
 
-        let mut entropy = vec![0u8; entropy_bytes_count]; // empty vector [0, 0, 0, 0, 0...] with length of 16 or 32 depends of mnemonic_type
+        // empty vector [0, 0, 0, 0, 0...] with length of 16 to 32 depends of mnemonic_type
+        // One extra byte of capacity for the checksum, so pushing it doesn't reallocate and leave a copy of the entropy behind
+        let mut entropy = Vec::with_capacity(entropy_bytes_count + 1);
+        entropy.resize(entropy_bytes_count, 0u8);
 
         // Fill the vector with random bytes
-        rng.fill_bytes(&mut entropy); // [123, 23, 123, 23, 123...]
-        entropy
+        if let Err(e) = rng.try_fill_bytes(&mut entropy) {
+            entropy.zeroize(); // Part of the buffer may already be filled
+            return Err(MnemonicError::GeneratorError(e));
+        }
+        Ok(entropy) // [123, 23, 123, 23, 123...]
     }
 
-    fn generate_checksum(entropy: &Vec<u8>, mnemonic_type: MnemonicType) -> u8 {
-        let hash = digest(entropy); // Hash the entropy using sha256 which returns it in hexadecimal
+    fn generate_checksum(entropy: &[u8], mnemonic_type: MnemonicType) -> u8 {
+        // Hash the entropy using sha256, the hash stays on the stack and is wiped after use
+        let mut hash = Sha256::digest(entropy);
 
-        if hash.len() < 2 {
-            panic!("Hash must be at least 2 characters.");
-        }
+        // The checksum is always taken from the first byte of the hash (4 to 8 bits)
+        let first_byte = hash[0];
+        hash.zeroize();
 
-        let checksum_bits = mnemonic_type.bits() / 32;
-        let checksum_index = if checksum_bits == 4 {1} else if checksum_bits == 8 {2} else {0}; // i take 4 bits or 8 bits
-
-        let checksum = &hash[..checksum_index]; // checksum in hexadecimal
-        u8::from_str_radix(checksum, 16).expect("Failed to parse checksum as u8") // I convert hexadecimal to decimal in order to append in my raw entropy
+        // I keep only the leading `checksum_bits` bits, as decimal in order to append in my raw entropy
+        first_byte >> (8 - mnemonic_type.checksum_bits())
     }
 
-    fn convert_entropy_to_binary(entropy: &Vec<u8>) -> String {
-        // [123, 231 ,123 ,123 ,43 ,123, 231(checksum)] => 0011100111011001110011
-        let mut binary_entropy = String::new();
+    fn decode_words(
+        words: &[&str],
+        lang: &dyn WordList,
+        abbreviations: bool,
+    ) -> Result<(MnemonicType, Zeroizing<Vec<u8>>, u8), MnemonicError> {
+        /*
+            Every word is mapped back to its 11-bit index in the wordlist,
+            the indexes are then unpacked into entropy bytes and the checksum bits of the last word.
+            Returns the raw entropy and the checksum bits found in the phrase, the checksum is not verified here.
+        */
+        let mnemonic_type = MnemonicType::from_words_count(words.len())
+            .ok_or(MnemonicError::BadWordCount(words.len()))?;
 
-        for el in entropy {
-            // Ensure each byte is represented by exactly 8 bits
-            let binary_repr = format!("{:08b}", el);
-            binary_entropy += &binary_repr
+        let mut indices = Zeroizing::new(Vec::with_capacity(words.len()));
+
+        for (position, word) in words.iter().enumerate() {
+            let found = match abbreviations {
+                true => lang.expand_prefix_index(word),
+                false => lang.index_of(word),
+            };
+            let index = match found {
+                Some(index) => index,
+                None if abbreviations && lang.complete(word).nth(1).is_some() => {
+                    return Err(MnemonicError::AmbiguousWord { index: position, word: word.to_string() });
+                }
+                None => return Err(MnemonicError::UnknownWord { index: position, word: word.to_string() }),
+            };
+            indices.push(index);
         }
-        binary_entropy // => 011011001110111
-    }
 
-    fn mnemonic_phrase_generation(&mut self, binary_entropy: String) {
-        // Convert my raw entropy + checksum into binary, divide it into chunks of 11-bit each with length of 24 (words) or 12 (words)
-        let mut start_idx = 0;
-        let mut chunks = Vec::new(); // ["01000110110", "11100010110" ...] each chunk of 11bits for 24 len if Bit256
+        // One extra byte of capacity for the checksum pushed by from_phrase
+        let mut raw_entropy = Zeroizing::new(Vec::with_capacity(mnemonic_type.bytes() + 1));
+        raw_entropy.resize(mnemonic_type.bytes(), 0u8);
 
-        // Loop through the binary string and extract 11-bit chunks
-        while start_idx + 11 <= binary_entropy.len() {
-            // Extract the chunk of 11 bits starting at `start_idx`
-            chunks.push(binary_entropy.get(start_idx..start_idx + 11).unwrap());
-            start_idx += 11; // Move to the next chunk
-        }
+        let checksum_decimal = encoding::indices_to_entropy(&indices, &mut raw_entropy);
 
-        let wordlist = Language::get_predefined_word_list(&self.lang); // I take wordlist from language based on chosen one
+        Ok((mnemonic_type, raw_entropy, checksum_decimal))
+    }
+
+    fn mnemonic_phrase_generation(&mut self) {
+        // Pack my raw entropy + checksum into 11-bit word indexes, 12 to 24 of them depending on mnemonic_type
+        let mut indices = Zeroizing::new(vec![0u16; self.mnemonic_type.words_count()]);
+        encoding::entropy_to_indices(self.entropy(), self.checksum, self.mnemonic_type.checksum_bits(), &mut indices);
 
-        for chunk in chunks {
-            // I have some number calculated from my 11-bit binary from 0 to 2047 and i have wordlist with 2048
-            // I use this decimal representation as index to take word from my predefined list
-            let decimal = usize::from_str_radix(chunk, 2).unwrap(); // Convert binary to decimal
-            let phrase = wordlist[decimal];
+        for index in indices.iter() {
+            // Every index is from 0 to 2047 and i have wordlist with 2048, so i use it to take word from my predefined list
+            let phrase = self.wordlist.word_at(*index).expect("Word index is always 11 bits");
             self.add_mnemonic_phrase(String::from(phrase));
         }
     }
@@ -222,23 +439,94 @@ impl Mnemonic {
         self.mnemonic_phrase.push(word);
     }
 
-    fn validate_entropy(entropy: &Vec<u8>) -> Result<(), String> {
-        let entropy_bits = entropy.len() * 8;
-        if entropy_bits != 128 && entropy_bits != 256 {
-            return Err(format!(
-                "Invalid entropy length: expected 128 or 256 bits, but got {} bits",
-                entropy_bits
-            ));
+    fn validate_entropy(entropy: &[u8]) -> Result<(), MnemonicError> {
+        if MnemonicType::from_bytes_count(entropy.len()).is_none() {
+            return Err(MnemonicError::BadEntropyLength(entropy.len()));
         }
         Ok(())  // Return Ok if the entropy length is valid
     }
 }
 
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Mnemonic {
+    /// Serialized as its phrase, words joined with the separator of the wordlist
+    ///
+    /// This is one-way for a `CustomWordList`: only phrases of a predefined `Language` can be deserialized again.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.phrase())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Mnemonic {
+    /// Parsed in the language detected by `Language::detect`, every word must be spelled out in full
+    /// (abbreviations are not expanded) and invalid phrases are errors
+    ///
+    /// A phrase valid in several languages is accepted when every candidate decodes to the same entropy
+    /// (e.g. ChineseSimplified and ChineseTraditional share 1275 characters at the same index), the seed is then
+    /// the same too and the first candidate is used. Otherwise it is an `AmbiguousLanguage` error.
+    /// Phrases of a `CustomWordList` serialize but can never be deserialized.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Mnemonic, D::Error> {
+        struct PhraseVisitor;
+
+        impl serde::de::Visitor<'_> for PhraseVisitor {
+            type Value = Mnemonic;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "a BIP-39 mnemonic phrase")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, phrase: &str) -> Result<Mnemonic, E> {
+                match Language::detect(phrase) {
+                    Ok(lang) => Mnemonic::from_exact_phrase(phrase, lang),
+                    Err(MnemonicError::AmbiguousLanguage(candidates)) => {
+                        let mnemonics = candidates
+                            .iter()
+                            .map(|lang| Mnemonic::from_exact_phrase(phrase, *lang))
+                            .collect::<Result<Vec<_>, _>>()
+                            .map_err(E::custom)?;
+
+                        match mnemonics.iter().all(|mnemonic| mnemonic.entropy() == mnemonics[0].entropy()) {
+                            true => Ok(mnemonics.into_iter().next().expect("Ambiguous phrases have candidates")),
+                            false => Err(MnemonicError::AmbiguousLanguage(candidates)),
+                        }
+                    }
+                    Err(e) => Err(e),
+                }
+                .map_err(E::custom)
+            }
+
+            fn visit_string<E: serde::de::Error>(self, phrase: String) -> Result<Mnemonic, E> {
+                // The deserializer hands over its own copy of the phrase, it is wiped once parsed
+                let phrase = Zeroizing::new(phrase);
+                self.visit_str(&phrase)
+            }
+        }
+
+        deserializer.deserialize_string(PhraseVisitor)
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_hex_to_binary() {
+        assert_eq!(hex_to_binary("7f0a"), "0111111100001010");
+        assert_eq!(hex_to_binary(""), "");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    #[should_panic(expected = "Invalid hex string")]
+    fn test_deprecated_hex_to_binary_panics_on_invalid_hex() {
+        hex_to_binary("7g");
+    }
+
     #[test]
     fn test_valid_entropy_vector() {
         // Example valid entropy in decimal (corresponding to 128 bits)
@@ -248,17 +536,17 @@ mod tests {
             16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31
         ];
 
-        let entropy_binary128 = Mnemonic::convert_entropy_to_binary(&entropy128);
-        let entropy_binary256 = Mnemonic::convert_entropy_to_binary(&entropy256);
-
         let validate128 = Mnemonic::validate_entropy(&entropy128);
         let validate256 = Mnemonic::validate_entropy(&entropy256);
 
         assert!(validate128.is_ok(), "Entropy should correct");
         assert!(validate256.is_ok(), "Entropy should be correct");
 
-        assert_eq!(entropy_binary128.len(), 128, "Binary length for entropy128 should be 128 bits");
-        assert_eq!(entropy_binary256.len(), 256, "Binary length for entropy256 should be 256 bits");
+        let mnemonic128 = Mnemonic::from_entropy(&entropy128, Language::English).unwrap();
+        let mnemonic256 = Mnemonic::from_entropy(&entropy256, Language::English).unwrap();
+
+        assert_eq!(mnemonic128.mnemonic_phrase().len(), 12, "Entropy128 should be encoded in 12 words");
+        assert_eq!(mnemonic256.mnemonic_phrase().len(), 24, "Entropy256 should be encoded in 24 words");
     }
 
     #[test]
@@ -269,25 +557,19 @@ mod tests {
             16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30
         ];
 
-        let entropy_binary128 = Mnemonic::convert_entropy_to_binary(&entropy128);
-        let entropy_binary256 = Mnemonic::convert_entropy_to_binary(&entropy256);
-
         let validate128 = Mnemonic::validate_entropy(&entropy128);
         let validate256 = Mnemonic::validate_entropy(&entropy256);
 
         assert!(validate128.is_err(), "Entropy is invalid");
         assert!(validate256.is_err(), "Entropy is invalid");
 
-        assert_ne!(entropy_binary128.len(), 128, "Expected entropy128 binary length to be 128, but it's not.");
-        assert_ne!(entropy_binary256.len(), 256, "Expected entropy256 binary length to be 256, but it's not.");
+        assert!(matches!(Mnemonic::from_entropy(&entropy128, Language::English), Err(MnemonicError::BadEntropyLength(15))));
+        assert!(matches!(Mnemonic::from_entropy(&entropy256, Language::English), Err(MnemonicError::BadEntropyLength(31))));
     }
 
     #[test]
     fn test_valid_mnemonic_phrase() {
         let lang = Language::English;
-        let mnemonic_type = MnemonicType::Bits128;
-
-        let mut mnemonic = Mnemonic::new(lang, mnemonic_type);
 
         let test_data = [
             "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
@@ -295,25 +577,408 @@ mod tests {
             "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607"
         ];
 
-        let mut binary_hex = hex_to_binary(&test_data[0]);
         let entropy_bytes = hex::decode(test_data[0]).expect("Invalid hex string");
-        let hash = digest(entropy_bytes);
-
-        // Decode the hash into bytes
-        let hash_bytes = hex::decode(hash).expect("Invalid hash string");
+        let hash_bytes = Sha256::digest(&entropy_bytes);
 
         // Extract the first 4 bits from the first byte of the hash
-        let checksum_binary = format!("{:08b}", hash_bytes[0]); // Convert first byte to binary
-        let checksum_binary = &checksum_binary[0..4]; // Take only the first 4 bits
+        let checksum = hash_bytes[0] >> 4;
 
-        binary_hex += checksum_binary;
+        let mut indices = [0u16; 12];
+        encoding::entropy_to_indices(&entropy_bytes, checksum, 4, &mut indices);
 
+        let wordlist = Language::get_predefined_word_list(&lang);
+        let phrases: Vec<&str> = indices.iter().map(|index| wordlist[*index as usize]).collect();
         let expected_phrases: Vec<&str> = test_data[1].split_whitespace().collect();
 
-        mnemonic.mnemonic_phrase_generation(binary_hex);
+        assert_eq!(phrases, expected_phrases);
+    }
+
+    #[test]
+    fn test_reverse_index_for_every_language() {
+        for language in Language::ALL {
+            let wordlist = Language::get_predefined_word_list(&language);
+
+            for (index, word) in wordlist.iter().enumerate() {
+                assert_eq!(language.index_of(word), Some(index as u16), "{:?} word {}", language, word);
+                assert_eq!(language.word_at(index as u16), Some(*word));
+            }
+
+            assert_eq!(language.index_of("notaword"), None);
+            assert_eq!(language.word_at(2048), None);
+        }
+
+        // NFC input is normalized before the lookup
+        assert_eq!(Language::Spanish.index_of("\u{e1}baco"), Some(0));
+    }
+
+    #[test]
+    fn test_from_phrase() {
+        let phrase = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).expect("Phrase should be valid");
+
+        assert_eq!(mnemonic.entropy[..16], [0x7f; 16]);
+        assert_eq!(mnemonic.mnemonic_phrase().join(" "), phrase);
+        assert!(mnemonic.validate_checksum().unwrap());
+
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+            abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).expect("Phrase should be valid");
+        assert_eq!(mnemonic.entropy[..32], [0u8; 32]);
+    }
+
+    #[test]
+    fn test_prefix_expansion_and_completion() {
+        assert_eq!(Language::English.expand_prefix("aban"), Some("abandon"));
+        assert_eq!(Language::English.expand_prefix("act"), Some("act"));
+        assert_eq!(Language::English.expand_prefix("ab"), None);
+        assert_eq!(Language::English.expand_prefix("xyz"), None);
+        assert_eq!(Language::Spanish.expand_prefix("\u{e1}ba"), Some("a\u{301}baco"));
+
+        let completions: Vec<&str> = Language::English.complete("abs").collect();
+        assert_eq!(completions, ["absent", "absorb", "abstract", "absurd"]);
+        assert_eq!(Language::English.complete("").count(), 2048);
+        assert_eq!(Language::English.complete("xyz").count(), 0);
+
+        // Every word of the four-letter languages is identified by its first four letters
+        for language in [Language::English, Language::Spanish, Language::French, Language::Italian, Language::Portuguese, Language::Czech] {
+            for word in Language::get_predefined_word_list(&language) {
+                let nfc_word: String = unicode_normalization::UnicodeNormalization::nfc(*word).collect();
+                let prefix: String = nfc_word.chars().take(4).collect();
+                assert_eq!(language.expand_prefix(&prefix), Some(*word), "{:?} prefix {}", language, prefix);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_abbreviated_phrase() {
+        let abbreviated = "lega winn than year wave saus wort usef lega winn than yell";
+        let mnemonic = Mnemonic::from_phrase(abbreviated, Language::English).expect("Abbreviated phrase should be valid");
+
+        assert_eq!(mnemonic.entropy(), &[0x7f; 16]);
+        assert_eq!(
+            mnemonic.mnemonic_phrase().join(" "),
+            "legal winner thank year wave sausage worth useful legal winner thank yellow"
+        );
+
+        let ambiguous = "lega winn than year wave saus wort us lega winn than yell";
+        assert!(matches!(
+            Mnemonic::from_phrase(ambiguous, Language::English),
+            Err(MnemonicError::AmbiguousWord { index: 7, word }) if word == "us"
+        ));
+    }
+
+    #[test]
+    fn test_from_phrase_invalid() {
+        let unknown_word = "legal winner thank year wave sausage worth useful legal winner thank yellowish";
+        let bad_count = "legal winner thank year wave sausage worth useful legal winner thank";
+        let bad_checksum = "legal winner thank year wave sausage worth useful legal winner thank thank";
+
+        assert!(matches!(
+            Mnemonic::from_phrase(unknown_word, Language::English),
+            Err(MnemonicError::UnknownWord { index: 11, word }) if word == "yellowish"
+        ));
+        assert!(matches!(Mnemonic::from_phrase(bad_count, Language::English), Err(MnemonicError::BadWordCount(11))));
+        assert!(matches!(
+            Mnemonic::from_phrase(bad_checksum, Language::English),
+            Err(MnemonicError::InvalidChecksum { expected: 0x0c, found: 0x0e })
+        ));
+    }
+
+    #[test]
+    fn test_validate_checksum_detects_tampered_phrase() {
+        let phrase = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        let mut mnemonic = Mnemonic::from_phrase(phrase, Language::English).expect("Phrase should be valid");
+        assert!(mnemonic.validate_checksum().unwrap());
+
+        // Stored entropy and checksum stay the same, only the phrase is changed
+        mnemonic.mnemonic_phrase[3] = String::from("wave");
+        assert!(!mnemonic.validate_checksum().unwrap());
+
+        mnemonic.mnemonic_phrase[3] = String::from("yearly");
+        assert!(matches!(mnemonic.validate_checksum(), Err(MnemonicError::UnknownWord { index: 3, .. })));
+    }
+
+    #[test]
+    fn test_from_entropy() {
+        let test_data = [
+            ("80808080808080808080808080808080", "letter advice cage absurd amount doctor acoustic avoid letter advice cage above"),
+            ("ffffffffffffffffffffffffffffffff", "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal will"
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art"
+            ),
+        ];
+
+        for (entropy_hex, phrase) in test_data {
+            let entropy = hex::decode(entropy_hex).expect("Invalid hex string");
+            let mnemonic = Mnemonic::from_entropy(&entropy, Language::English).expect("Entropy should be valid");
+
+            assert_eq!(mnemonic.mnemonic_phrase().join(" "), phrase.split_whitespace().collect::<Vec<_>>().join(" "));
+            assert_eq!(mnemonic.entropy(), entropy.as_slice());
+        }
+
+        assert!(matches!(Mnemonic::from_entropy(&[0u8; 15], Language::English), Err(MnemonicError::BadEntropyLength(15))));
+    }
+
+    #[test]
+    fn test_to_seed_trezor_vectors() {
+        // Official Trezor test vectors, all of them use "TREZOR" as passphrase
+        let test_data = [
+            (
+                "00000000000000000000000000000000",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607"
+            ),
+            (
+                "80808080808080808080808080808080",
+                "d71de856f81a8acc65e6fc851a38d4d7ec216fd0796d0a6827a3ad6ed5511a30fa280f12eb2e47ed2ac03b5c462a0358d18d69fe4f985ec81778c1b370b652a8"
+            ),
+            (
+                "ffffffffffffffffffffffffffffffff",
+                "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069"
+            ),
+            (
+                "9e885d952ad362caeb4efe34a8e91bd2",
+                "274ddc525802f7c828d8ef7ddbcdc5304e87ac3535913611fbbfa986d0c9e5476c91689f9c8a54fd55bd38606aa6a8595ad213d4c9c9f9aca3fb217069a41028"
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8"
+            ),
+        ];
+
+        for (entropy_hex, seed_hex) in test_data {
+            let entropy = hex::decode(entropy_hex).expect("Invalid hex string");
+            let mnemonic = Mnemonic::from_entropy(&entropy, Language::English).expect("Entropy should be valid");
+
+            assert_eq!(hex::encode(mnemonic.to_seed("TREZOR")), seed_hex);
+        }
+    }
+
+    #[test]
+    fn test_to_seed_without_passphrase() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).expect("Phrase should be valid");
+
+        assert_eq!(
+            hex::encode(mnemonic.to_seed("")),
+            "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4"
+        );
+    }
+
+    #[test]
+    fn test_detect_language() {
+        let english = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        assert_eq!(Language::detect(english).unwrap(), Language::English);
+
+        for language in Language::ALL {
+            let mnemonic = Mnemonic::from_entropy(&[0x7f; 32], language).expect("Entropy should be valid");
+            let phrase = mnemonic.mnemonic_phrase().join(" ");
+
+            match (language, Language::detect(&phrase)) {
+                (_, Ok(detected)) => assert_eq!(detected, language),
+                // Only the Chinese wordlists share characters at the same index
+                (Language::ChineseSimplified | Language::ChineseTraditional, Err(MnemonicError::AmbiguousLanguage(candidates))) => {
+                    assert!(candidates.contains(&language))
+                }
+                (_, Err(e)) => panic!("Unexpected error for {:?}: {}", language, e),
+            }
+        }
+    }
+
+    #[test]
+    fn test_detect_language_ignores_abbreviations() {
+        // Every word is also a French word or a unique prefix of one ("age" of "agencer", "bid" of "bidule"...)
+        // and the checksum passes in French as well
+        let english = "abandon age arrive bid bullet carbon civil correct cycle divert dress box";
+        assert!(Mnemonic::from_phrase(english, Language::French).is_ok());
+        assert_eq!(Language::detect(english).unwrap(), Language::English);
+
+        let spanish_prefixes = "admit arm call cigar diet exist guard idea lab moment noble cigar";
+        assert!(Mnemonic::from_phrase(spanish_prefixes, Language::Spanish).is_ok());
+        assert_eq!(Language::detect(spanish_prefixes).unwrap(), Language::English);
+
+        // Abbreviated phrases are never detected
+        assert!(matches!(
+            Language::detect("lega winn than year wave saus wort usef lega winn than yell"),
+            Err(MnemonicError::UnknownWord { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_detect_language_ambiguous_and_unknown() {
+        // "的" and "在" have the same index in both Chinese wordlists
+        let chinese = "的 的 的 的 的 的 的 的 的 的 的 在";
+        assert!(matches!(
+            Language::detect(chinese),
+            Err(MnemonicError::AmbiguousLanguage(candidates))
+                if candidates == vec![Language::ChineseSimplified, Language::ChineseTraditional]
+        ));
+
+        let mistyped = "legal winner thank year wave sausage worth usefull legal winner thank yellow";
+        assert!(matches!(Language::detect(mistyped), Err(MnemonicError::UnknownWord { index: 7, .. })));
+
+        let bad_checksum = "legal winner thank year wave sausage worth useful legal winner thank thank";
+        assert!(matches!(Language::detect(bad_checksum), Err(MnemonicError::InvalidChecksum { .. })));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_try_new() {
+        let mnemonic = Mnemonic::try_new(Language::Korean, MnemonicType::Bits160).expect("Mnemonic should be generated");
+
+        assert_eq!(mnemonic.mnemonic_phrase().len(), 15);
+        assert!(mnemonic.is_valid());
+        assert_eq!(mnemonic.wordlist().language(), Some(Language::Korean));
+        assert_eq!(mnemonic.mnemonic_type, MnemonicType::Bits160);
+    }
+
+    #[test]
+    fn test_generate_with_seeded_rng() {
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        let mut rng = ChaCha20Rng::from_seed([7u8; 32]);
+        let first = Mnemonic::generate_with_rng(&mut rng, Language::English, MnemonicType::Bits256).unwrap();
+        let second = Mnemonic::generate_with_rng(&mut rng, Language::English, MnemonicType::Bits256).unwrap();
+
+        let mut same_rng = ChaCha20Rng::from_seed([7u8; 32]);
+        let replayed = Mnemonic::generate_with_rng(&mut same_rng, Language::English, MnemonicType::Bits256).unwrap();
+
+        // Same seed gives the same phrase, the RNG state moves forward between calls
+        assert_eq!(first.mnemonic_phrase(), replayed.mnemonic_phrase());
+        assert_ne!(first.mnemonic_phrase(), second.mnemonic_phrase());
+        assert!(first.validate_checksum().unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_generate_with_failing_rng() {
+        struct FailingRng;
+
+        impl RngCore for FailingRng {
+            fn next_u32(&mut self) -> u32 { unimplemented!() }
+            fn next_u64(&mut self) -> u64 { unimplemented!() }
+            fn fill_bytes(&mut self, _dest: &mut [u8]) { unimplemented!() }
+            fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                Err(rand_core::Error::new("hardware token disconnected"))
+            }
+        }
+
+        impl CryptoRng for FailingRng {}
+
+        let result = Mnemonic::generate_with_rng(&mut FailingRng, Language::English, MnemonicType::Bits128);
+        let error = result.err().expect("Generation should fail");
+
+        assert!(matches!(error, MnemonicError::GeneratorError(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
 
-        // When i create an instance i already create 12 phrases so when i use phrase_generation i just take those phrases to see if they are equal
-        assert_eq!(mnemonic.mnemonic_phrase[12..], expected_phrases);
+    #[test]
+    fn test_nfc_and_nfd_input_resolve_to_same_mnemonic() {
+        use unicode_normalization::UnicodeNormalization;
+
+        let mnemonic = Mnemonic::from_entropy(&[0x00; 16], Language::Spanish).expect("Entropy should be valid");
+        let phrase = mnemonic.mnemonic_phrase().join(" ");
+
+        let nfc_phrase: String = phrase.nfc().collect();
+        let nfd_phrase: String = phrase.nfd().collect();
+        assert_ne!(nfc_phrase, nfd_phrase, "Spanish phrase should contain accented characters");
+
+        let from_nfc = Mnemonic::from_phrase(&nfc_phrase, Language::Spanish).expect("NFC phrase should be valid");
+        let from_nfd = Mnemonic::from_phrase(&nfd_phrase, Language::Spanish).expect("NFD phrase should be valid");
+
+        assert_eq!(from_nfc.entropy(), from_nfd.entropy());
+        assert_eq!(from_nfc.to_seed("contraseña"), from_nfd.to_seed("contrasen\u{303}a"));
+    }
+
+    #[test]
+    fn test_japanese_vector_with_normalized_passphrase() {
+        // Phrase is separated with ideographic spaces and both inputs are typed in NFC
+        let phrase = "あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}\
+            あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あおぞら";
+        let passphrase = "㍍ガバヴァぱばぐゞちぢ十人十色";
+
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::Japanese).expect("Phrase should be valid");
+
+        assert_eq!(mnemonic.entropy(), &[0x00; 16]);
+        assert_eq!(mnemonic.phrase().matches('\u{3000}').count(), 11);
+        assert_eq!(
+            hex::encode(mnemonic.to_seed(passphrase)),
+            "a262d6fb6122ecf45be09c50492b31f92e9beb7d9a845987a02cefda57a15f9c467a17872029a9e92299b5cbdf306e3a0ee620245cbd508959b6cb7ca637bd55"
+        );
+    }
+
+    #[test]
+    fn test_custom_wordlist_matches_builtin_language() {
+        let words = Language::get_predefined_word_list(&Language::English);
+        let wordlist = Arc::new(CustomWordList::from_words(words).expect("English wordlist should be valid").with_separator("-"));
+
+        let custom = Mnemonic::from_entropy(&[0x7f; 16], wordlist.clone()).expect("Entropy should be valid");
+        let english = Mnemonic::from_entropy(&[0x7f; 16], Language::English).expect("Entropy should be valid");
+
+        assert_eq!(custom.mnemonic_phrase(), english.mnemonic_phrase());
+        assert_eq!(custom.to_seed("TREZOR"), english.to_seed("TREZOR"));
+        assert_eq!(custom.phrase().as_str(), english.phrase().replace(' ', "-"));
+        assert_eq!(custom.wordlist().language(), None);
+
+        let parsed = Mnemonic::from_phrase("lega winn than year wave saus wort usef lega winn than yell", wordlist)
+            .expect("Abbreviated phrase should be valid");
+        assert_eq!(parsed.entropy(), &[0x7f; 16]);
+    }
+
+    #[test]
+    fn test_to_language_keeps_entropy_but_changes_seed() {
+        let english = Mnemonic::from_phrase(
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            Language::English,
+        ).expect("Phrase should be valid");
+
+        let french = english.to_language(Language::French);
+        assert_eq!(french.entropy(), english.entropy());
+        assert_eq!(french.mnemonic_phrase()[0], "implorer");
+        assert!(french.validate_checksum().expect("Translated phrase should be valid"));
+
+        let seeds = english.compare_seeds(Language::French, "TREZOR");
+        assert!(!seeds.is_same());
+        assert_eq!(seeds.original, english.to_seed("TREZOR"));
+        assert_eq!(seeds.translated, french.to_seed("TREZOR"));
+
+        // Translating back restores the original words and seed
+        let back = french.to_language(Language::English);
+        assert_eq!(back.mnemonic_phrase(), english.mnemonic_phrase());
+        assert!(english.compare_seeds(Language::English, "TREZOR").is_same());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_generated_phrase_round_trip() {
+        let mnemonic_types = [
+            MnemonicType::Bits128,
+            MnemonicType::Bits160,
+            MnemonicType::Bits192,
+            MnemonicType::Bits224,
+            MnemonicType::Bits256,
+        ];
+
+        for mnemonic_type in mnemonic_types {
+            let mnemonic = Mnemonic::new(Language::English, mnemonic_type);
+            assert_eq!(mnemonic.mnemonic_phrase().len(), mnemonic_type.words_count());
+
+            let parsed = Mnemonic::from_phrase(&mnemonic.mnemonic_phrase().join(" "), Language::English)
+                .expect("Generated phrase should be valid");
+
+            assert_eq!(parsed.entropy, mnemonic.entropy);
+            assert_eq!(parsed.checksum, mnemonic.checksum);
+        }
     }
 
 }
\ No newline at end of file
@@ -0,0 +1,220 @@
+/*
+    SLIP-10 key derivation for ed25519, used by Solana, Stellar and most other ed25519 chains.
+
+    Same scheme as BIP-32 with another HMAC key ("ed25519 seed"), except that the child private key is
+    the left 32 bytes of the HMAC directly: ed25519 keys can't be added like secp256k1 scalars, so only
+    hardened children exist and there are no extended public keys.
+*/
+
+pub mod solana;
+pub mod stellar;
+
+use crate::bip32::{ChildNumber, DerivationPath};
+use crate::Mnemonic;
+use ed25519_dalek::SigningKey;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use zeroize::{Zeroize, Zeroizing};
+
+const MASTER_KEY_SALT: &[u8] = b"ed25519 seed";
+
+#[derive(Debug, PartialEq)]
+pub enum Slip10Error {
+    /// Seed length in bytes is not between 16 and 64
+    BadSeedLength(usize),
+    /// Normal (non-hardened) child requested, ed25519 only supports hardened derivation
+    NormalChild(ChildNumber),
+    /// Index is 2^31 or more, it can't be used as a hardened child
+    InvalidIndex(u32),
+    /// Key is already 255 levels deep
+    MaximumDepth,
+}
+
+impl core::fmt::Display for Slip10Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Slip10Error::BadSeedLength(bytes) => {
+                write!(f, "Invalid seed length: expected 16 to 64 bytes, but got {} bytes.", bytes)
+            }
+            Slip10Error::NormalChild(child) => {
+                write!(f, "Child {} is not hardened, ed25519 keys only derive hardened children.", child)
+            }
+            Slip10Error::InvalidIndex(index) => write!(f, "Index {} is above the maximum of 2^31 - 1.", index),
+            Slip10Error::MaximumDepth => write!(f, "Maximum depth of 255 derivations reached."),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Slip10Error {}
+
+/// ed25519 private key with its chain code and position in the key tree
+pub struct ExtendedKey {
+    depth: u8,
+    child_number: u32,
+    chain_code: [u8; 32],
+    private_key: Zeroizing<[u8; 32]>,
+}
+
+impl Drop for ExtendedKey {
+    /// Wipes the chain code, the private key wipes itself
+    fn drop(&mut self) {
+        self.chain_code.zeroize();
+    }
+}
+
+impl ExtendedKey {
+    /// Builds the master key (depth 0) from a BIP-39 seed
+    pub fn new_master(seed: &[u8]) -> Result<ExtendedKey, Slip10Error> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(Slip10Error::BadSeedLength(seed.len()));
+        }
+
+        let mut hmac = Hmac::<Sha512>::new_from_slice(MASTER_KEY_SALT).expect("HMAC accepts keys of any length");
+        hmac.update(seed);
+        Ok(Self::from_hmac(hmac, 0, 0))
+    }
+
+    /// Builds the master key from the seed of a mnemonic and its passphrase
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Result<ExtendedKey, Slip10Error> {
+        Self::new_master(&*mnemonic.to_seed(passphrase))
+    }
+
+    /// Derives a hardened child key
+    pub fn derive_child(&self, child: ChildNumber) -> Result<ExtendedKey, Slip10Error> {
+        let index = match child {
+            ChildNumber::Hardened(index) => child.checked_index().map_err(|_| Slip10Error::InvalidIndex(index))?,
+            ChildNumber::Normal(_) => return Err(Slip10Error::NormalChild(child)),
+        };
+        let depth = self.depth.checked_add(1).ok_or(Slip10Error::MaximumDepth)?;
+
+        let mut hmac = Hmac::<Sha512>::new_from_slice(&self.chain_code).expect("HMAC accepts keys of any length");
+        hmac.update(&[0]);
+        hmac.update(&*self.private_key);
+        hmac.update(&index.to_be_bytes());
+        Ok(Self::from_hmac(hmac, depth, index))
+    }
+
+    /// Derives the key at `path`, relative to this key, every child must be hardened
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedKey, Slip10Error> {
+        path.iter().try_fold(self.clone_key(), |key, child| key.derive_child(*child))
+    }
+
+    /// ed25519 signing key, also wiped when dropped
+    pub fn signing_key(&self) -> SigningKey {
+        SigningKey::from_bytes(&self.private_key)
+    }
+
+    /// Raw 32-byte private key (the ed25519 seed), wiped when dropped
+    pub fn private_key_bytes(&self) -> Zeroizing<[u8; 32]> {
+        self.private_key.clone()
+    }
+
+    /// 32-byte ed25519 public key
+    pub fn public_key_bytes(&self) -> [u8; 32] {
+        self.signing_key().verifying_key().to_bytes()
+    }
+
+    /// Number of derivations from the master key, 0 for the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Index of this key in its parent, always hardened (top bit set) except for the master key
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    fn from_hmac(hmac: Hmac<Sha512>, depth: u8, child_number: u32) -> ExtendedKey {
+        let output = Zeroizing::new(<[u8; 64]>::from(hmac.finalize().into_bytes()));
+
+        // Every 32-byte string is a valid ed25519 private key, unlike secp256k1 nothing has to be checked
+        ExtendedKey {
+            depth,
+            child_number,
+            chain_code: output[32..].try_into().expect("Right half of HMAC-SHA512 is 32 bytes"),
+            private_key: Zeroizing::new(output[..32].try_into().expect("Left half of HMAC-SHA512 is 32 bytes")),
+        }
+    }
+
+    fn clone_key(&self) -> ExtendedKey {
+        ExtendedKey {
+            depth: self.depth,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            private_key: self.private_key.clone(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (path, chain code, private key, public key)
+    type Vector = (&'static str, &'static str, &'static str, &'static str);
+
+    // (seed, chain) of the ed25519 test vectors 1 and 2 of SLIP-10
+    const VECTORS: [(&str, [Vector; 6]); 2] = [
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            [
+                ("m", "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb", "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7", "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed"),
+                ("m/0'", "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69", "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3", "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c"),
+                ("m/0'/1'", "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14", "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2", "1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187"),
+                ("m/0'/1'/2'", "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c", "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9", "ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1"),
+                ("m/0'/1'/2'/2'", "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc", "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662", "8abae2d66361c879b900d204ad2cc4984fa2aa344dd7ddc46007329ac76c429c"),
+                ("m/0'/1'/2'/2'/1000000000'", "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230", "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793", "3c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a"),
+            ],
+        ),
+        (
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+            [
+                ("m", "ef70a74db9c3a5af931b5fe73ed8e1a53464133654fd55e7a66f8570b8e33c3b", "171cb88b1b3c1db25add599712e36245d75bc65a1a5c9e18d76f9f2b1eab4012", "8fe9693f8fa62a4305a140b9764c5ee01e455963744fe18204b4fb948249308a"),
+                ("m/0'", "0b78a3226f915c082bf118f83618a618ab6dec793752624cbeb622acb562862d", "1559eb2bbec5790b0c65d8693e4d0875b1747f4970ae8b650486ed7470845635", "86fab68dcb57aa196c77c5f264f215a112c22a912c10d123b0d03c3c28ef1037"),
+                ("m/0'/2147483647'", "138f0b2551bcafeca6ff2aa88ba8ed0ed8de070841f0c4ef0165df8181eaad7f", "ea4f5bfe8694d8bb74b7b59404632fd5968b774ed545e810de9c32a4fb4192f4", "5ba3b9ac6e90e83effcd25ac4e58a1365a9e35a3d3ae5eb07b9e4d90bcf7506d"),
+                ("m/0'/2147483647'/1'", "73bd9fff1cfbde33a1b846c27085f711c0fe2d66fd32e139d3ebc28e5a4a6b90", "3757c7577170179c7868353ada796c839135b3d30554bbb74a4b1e4a5a58505c", "2e66aa57069c86cc18249aecf5cb5a9cebbfd6fadeab056254763874a9352b45"),
+                ("m/0'/2147483647'/1'/2147483646'", "0902fe8a29f9140480a00ef244bd183e8a13288e4412d8389d140aac1794825a", "5837736c89570de861ebc173b1086da4f505d4adb387c6a1b1342d5e4ac9ec72", "e33c0f7d81d843c572275f287498e8d408654fdf0d1e065b84e2e6f157aab09b"),
+                ("m/0'/2147483647'/1'/2147483646'/2'", "5d70af781f3a37b829f0d060924d5e960bdc02e85423494afc0b1a41bbe196d4", "551d333177df541ad876a60ea71f00447931c0a9da16f227c11ea080d7391b8d", "47150c75db263559a70d5778bf36abbab30fb061ad69f69ece61a72b0cfa4fc0"),
+            ],
+        ),
+    ];
+
+    #[test]
+    fn test_slip10_vectors() {
+        for (seed, chain) in VECTORS {
+            let master = ExtendedKey::new_master(&hex::decode(seed).unwrap()).unwrap();
+
+            for (path, chain_code, private_key, public_key) in chain {
+                let path: DerivationPath = path.parse().unwrap();
+                let key = master.derive_path(&path).unwrap();
+
+                assert_eq!(hex::encode(key.chain_code()), chain_code, "{}", path);
+                assert_eq!(hex::encode(*key.private_key_bytes()), private_key, "{}", path);
+                assert_eq!(hex::encode(key.public_key_bytes()), public_key, "{}", path);
+                assert_eq!(key.depth() as usize, path.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_normal_children_are_rejected() {
+        let master = ExtendedKey::new_master(&[1; 32]).unwrap();
+
+        assert_eq!(
+            master.derive_path(&"m/44'/0".parse().unwrap()).err(),
+            Some(Slip10Error::NormalChild(ChildNumber::Normal(0)))
+        );
+        assert_eq!(ExtendedKey::new_master(&[0; 65]).err(), Some(Slip10Error::BadSeedLength(65)));
+        assert_eq!(solana::Keypair::path(1 << 31).err(), Some(Slip10Error::InvalidIndex(1 << 31)));
+
+        // Hardened(2^31 + 1) must not alias the child 1'
+        let child = ChildNumber::Hardened((1 << 31) + 1);
+        assert_eq!(master.derive_child(child).err(), Some(Slip10Error::InvalidIndex((1 << 31) + 1)));
+    }
+}
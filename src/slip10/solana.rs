@@ -0,0 +1,121 @@
+/*
+    Solana keypairs at m/44'/501'/account'/0', the path of Phantom, Solflare and `solana-keygen recover`
+    with the `prompt://?full-path=m/44'/501'/0'/0'` keypair URL.
+*/
+
+use super::{ExtendedKey, Slip10Error};
+use crate::bip32::{ChildNumber, DerivationPath};
+use crate::Mnemonic;
+use alloc::string::String;
+use core::fmt::Write;
+use zeroize::Zeroizing;
+
+/// Registered SLIP-44 coin type of Solana
+pub const COIN_TYPE: u32 = 501;
+
+/// ed25519 keypair of a Solana account
+pub struct Keypair {
+    account: u32,
+    key: ExtendedKey,
+}
+
+impl Keypair {
+    /// Derives the keypair of `account` from the SLIP-10 master key
+    pub fn new(master: &ExtendedKey, account: u32) -> Result<Keypair, Slip10Error> {
+        Ok(Keypair { account, key: master.derive_path(&Self::path(account)?)? })
+    }
+
+    /// Derives the keypair of `account` from the seed of a mnemonic and its passphrase
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str, account: u32) -> Result<Keypair, Slip10Error> {
+        Self::new(&ExtendedKey::from_mnemonic(mnemonic, passphrase)?, account)
+    }
+
+    /// Path of an account, m/44'/501'/account'/0'
+    pub fn path(account: u32) -> Result<DerivationPath, Slip10Error> {
+        Ok(DerivationPath::from(alloc::vec![
+            ChildNumber::Hardened(44),
+            ChildNumber::Hardened(COIN_TYPE),
+            ChildNumber::hardened(account).map_err(|_| Slip10Error::InvalidIndex(account))?,
+            ChildNumber::Hardened(0),
+        ]))
+    }
+
+    pub fn account(&self) -> u32 {
+        self.account
+    }
+
+    /// Base58 public key, the address of the account
+    pub fn pubkey(&self) -> String {
+        bs58::encode(self.key.public_key_bytes()).into_string()
+    }
+
+    /// 64 bytes of the keypair: the private key followed by the public key
+    pub fn to_bytes(&self) -> Zeroizing<[u8; 64]> {
+        let mut bytes = Zeroizing::new([0u8; 64]);
+        bytes[..32].copy_from_slice(&*self.key.private_key_bytes());
+        bytes[32..].copy_from_slice(&self.key.public_key_bytes());
+        bytes
+    }
+
+    /// Keypair file of `solana-keygen`, a JSON array of the 64 bytes, wiped when dropped
+    pub fn to_json(&self) -> Zeroizing<String> {
+        // Allocated once with the maximum length, so no copy of the secret is left behind by a reallocation
+        let mut json = Zeroizing::new(String::with_capacity(2 + 64 * 4));
+
+        json.push('[');
+        for (position, byte) in self.to_bytes().iter().enumerate() {
+            if position > 0 {
+                json.push(',');
+            }
+            write!(json, "{}", byte).expect("Writing to a String never fails");
+        }
+        json.push(']');
+        json
+    }
+
+    /// SLIP-10 key of the account, for signing with ed25519-dalek
+    pub fn key(&self) -> &ExtendedKey {
+        &self.key
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_phantom_accounts() {
+        let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).unwrap();
+        let master = ExtendedKey::from_mnemonic(&mnemonic, "").unwrap();
+
+        let expected = [
+            "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk",
+            "Hh8QwFUA6MtVu1qAoq12ucvFHNwCcVTV7hpWjeY1Hztb",
+            "7WktogJEd2wQ9eH2oWusmcoFTgeYi6rS632UviTBJ2jm",
+        ];
+        for (account, pubkey) in expected.into_iter().enumerate() {
+            assert_eq!(Keypair::new(&master, account as u32).unwrap().pubkey(), pubkey);
+        }
+        assert_eq!(Keypair::path(1).unwrap().to_string(), "m/44'/501'/1'/0'");
+    }
+
+    #[test]
+    fn test_keypair_json() {
+        let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).unwrap();
+        let keypair = Keypair::from_mnemonic(&mnemonic, "", 0).unwrap();
+
+        let json = keypair.to_json();
+        assert!(json.starts_with("[55,223,87,59,"));
+        assert!(json.ends_with(']') && !json.contains(' '));
+
+        let bytes: Vec<u8> = json[1..json.len() - 1].split(',').map(|byte| byte.parse().unwrap()).collect();
+        assert_eq!(bytes, keypair.to_bytes().to_vec());
+        assert_eq!(bs58::encode(&bytes[32..]).into_string(), keypair.pubkey());
+    }
+}
@@ -0,0 +1,136 @@
+/*
+    Stellar keypairs of SEP-0005 at m/44'/148'/account'.
+
+    Keys are printed as strkeys: a version byte (G... for public keys, S... for secret seeds), the 32-byte key
+    and a CRC16-XModem checksum in little-endian, encoded in unpadded RFC 4648 base32.
+*/
+
+use super::{ExtendedKey, Slip10Error};
+use crate::bip32::{ChildNumber, DerivationPath};
+use crate::Mnemonic;
+use alloc::string::String;
+use zeroize::Zeroizing;
+
+/// Registered SLIP-44 coin type of Stellar
+pub const COIN_TYPE: u32 = 148;
+
+const PUBLIC_KEY_VERSION: u8 = 6 << 3; // G
+const SECRET_SEED_VERSION: u8 = 18 << 3; // S
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// ed25519 keypair of a Stellar account
+pub struct Keypair {
+    account: u32,
+    key: ExtendedKey,
+}
+
+impl Keypair {
+    /// Derives the keypair of `account` from the SLIP-10 master key
+    pub fn new(master: &ExtendedKey, account: u32) -> Result<Keypair, Slip10Error> {
+        Ok(Keypair { account, key: master.derive_path(&Self::path(account)?)? })
+    }
+
+    /// Derives the keypair of `account` from the seed of a mnemonic and its passphrase
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str, account: u32) -> Result<Keypair, Slip10Error> {
+        Self::new(&ExtendedKey::from_mnemonic(mnemonic, passphrase)?, account)
+    }
+
+    /// Path of an account, m/44'/148'/account'
+    pub fn path(account: u32) -> Result<DerivationPath, Slip10Error> {
+        Ok(DerivationPath::from(alloc::vec![
+            ChildNumber::Hardened(44),
+            ChildNumber::Hardened(COIN_TYPE),
+            ChildNumber::hardened(account).map_err(|_| Slip10Error::InvalidIndex(account))?,
+        ]))
+    }
+
+    pub fn account(&self) -> u32 {
+        self.account
+    }
+
+    /// Public key strkey (G...), the account ID
+    pub fn public_key(&self) -> String {
+        encode_strkey(PUBLIC_KEY_VERSION, &self.key.public_key_bytes())
+    }
+
+    /// Secret seed strkey (S...), the string is wiped when dropped
+    pub fn secret_seed(&self) -> Zeroizing<String> {
+        Zeroizing::new(encode_strkey(SECRET_SEED_VERSION, &self.key.private_key_bytes()))
+    }
+
+    /// SLIP-10 key of the account, for signing with ed25519-dalek
+    pub fn key(&self) -> &ExtendedKey {
+        &self.key
+    }
+}
+
+fn encode_strkey(version: u8, key: &[u8; 32]) -> String {
+    let mut data = Zeroizing::new([0u8; 35]);
+    data[0] = version;
+    data[1..33].copy_from_slice(key);
+    let checksum = crc16_xmodem(&data[..33]);
+    data[33..].copy_from_slice(&checksum.to_le_bytes());
+
+    // 35 bytes are 280 bits, exactly 56 base32 characters without padding
+    let mut encoded = String::with_capacity(56);
+    for chunk in data.chunks(5) {
+        let bits = chunk.iter().fold(0u64, |bits, byte| bits << 8 | *byte as u64);
+        for position in (0..8).rev() {
+            encoded.push(BASE32_ALPHABET[(bits >> (5 * position) & 0x1f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, byte| {
+        (0..8).fold(crc ^ (*byte as u16) << 8, |crc, _| {
+            if crc & 0x8000 != 0 { crc << 1 ^ 0x1021 } else { crc << 1 }
+        })
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    fn keypairs(phrase: &str, count: u32) -> Vec<(String, String)> {
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+        let master = ExtendedKey::from_mnemonic(&mnemonic, "").unwrap();
+
+        (0..count)
+            .map(|account| {
+                let keypair = Keypair::new(&master, account).unwrap();
+                (keypair.public_key(), keypair.secret_seed().to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sep5_vector_1() {
+        let keypairs = keypairs("illness spike retreat truth genius clock brain pass fit cave bargain toe", 3);
+
+        assert_eq!(keypairs[0].0, "GDRXE2BQUC3AZNPVFSCEZ76NJ3WWL25FYFK6RGZGIEKWE4SOOHSUJUJ6");
+        assert_eq!(keypairs[0].1, "SBGWSG6BTNCKCOB3DIFBGCVMUPQFYPA2G4O34RMTB343OYPXU5DJDVMN");
+        assert_eq!(keypairs[1].0, "GBAW5XGWORWVFE2XTJYDTLDHXTY2Q2MO73HYCGB3XMFMQ562Q2W2GJQX");
+        assert_eq!(keypairs[1].1, "SCEPFFWGAG5P2VX5DHIYK3XEMZYLTYWIPWYEKXFHSK25RVMIUNJ7CTIS");
+        assert_eq!(keypairs[2].0, "GAY5PRAHJ2HIYBYCLZXTHID6SPVELOOYH2LBPH3LD4RUMXUW3DOYTLXW");
+        assert_eq!(keypairs[2].1, "SDAILLEZCSA67DUEP3XUPZJ7NYG7KGVRM46XA7K5QWWUIGADUZCZWTJP");
+    }
+
+    #[test]
+    fn test_sep5_abandon_about() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let keypairs = keypairs(phrase, 2);
+
+        assert_eq!(keypairs[0].0, "GB3JDWCQJCWMJ3IILWIGDTQJJC5567PGVEVXSCVPEQOTDN64VJBDQBYX");
+        assert_eq!(keypairs[0].1, "SBUV3MRWKNS6AYKZ6E6MOUVF2OYMON3MIUASWL3JLY5E3ISDJFELYBRZ");
+        assert_eq!(keypairs[1].0, "GDVSYYTUAJ3ACHTPQNSTQBDQ4LDHQCMNY4FCEQH5TJUMSSLWQSTG42MV");
+        assert_eq!(keypairs[1].1, "SCHDCVCWGAKGIMTORV6K5DYYV3BY4WG3RA4M6MCBGJLHUCWU2MC6DL66");
+        assert_eq!(Keypair::path(1).unwrap().to_string(), "m/44'/148'/1'");
+    }
+}
@@ -1,32 +1,82 @@
-use crate::{MAX_WORDS, MIN_WORDS};
-
-
-
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum MnemonicType {
     Bits128, // 128 bits of entropy -> 16 bytes (128 bits / 8)
+    Bits160, // 160 bits of entropy -> 20 bytes (160 bits / 8)
+    Bits192, // 192 bits of entropy -> 24 bytes (192 bits / 8)
+    Bits224, // 224 bits of entropy -> 28 bytes (224 bits / 8)
     Bits256, // 256 bits of entropy -> 32 bytes (256 bits / 8)
 }
 
 impl MnemonicType {
     pub const fn bytes(&self) -> usize {
-        match self {
-            MnemonicType::Bits128 => 16,
-            MnemonicType::Bits256 => 32,
-        }
+        self.bits() / 8
     }
 
     pub const fn bits(&self) -> usize {
         match self {
             MnemonicType::Bits128 => 128,
+            MnemonicType::Bits160 => 160,
+            MnemonicType::Bits192 => 192,
+            MnemonicType::Bits224 => 224,
             MnemonicType::Bits256 => 256,
         }
     }
 
+    /// Checksum is one bit for every 32 bits of entropy, from 4 (Bits128) up to 8 (Bits256)
+    pub const fn checksum_bits(&self) -> usize {
+        self.bits() / 32
+    }
+
     pub const fn words_count(&self) -> usize {
-        match self {
-            MnemonicType::Bits128 => MIN_WORDS,
-            MnemonicType::Bits256 => MAX_WORDS,
+        // Every word carries 11 bits of entropy + checksum
+        (self.bits() + self.checksum_bits()) / 11
+    }
+
+    /// Resolves the mnemonic type from the length of the entropy in bytes
+    pub const fn from_bytes_count(bytes_count: usize) -> Option<MnemonicType> {
+        match bytes_count {
+            16 => Some(MnemonicType::Bits128),
+            20 => Some(MnemonicType::Bits160),
+            24 => Some(MnemonicType::Bits192),
+            28 => Some(MnemonicType::Bits224),
+            32 => Some(MnemonicType::Bits256),
+            _ => None,
         }
     }
+
+    /// Resolves the mnemonic type from the number of words in a phrase
+    pub const fn from_words_count(words_count: usize) -> Option<MnemonicType> {
+        match words_count {
+            12 => Some(MnemonicType::Bits128),
+            15 => Some(MnemonicType::Bits160),
+            18 => Some(MnemonicType::Bits192),
+            21 => Some(MnemonicType::Bits224),
+            24 => Some(MnemonicType::Bits256),
+            _ => None,
+        }
+    }
+}
+
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MnemonicType {
+    /// Serialized as its number of words, e.g. `24`
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.words_count() as u64)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MnemonicType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<MnemonicType, D::Error> {
+        let words_count = u64::deserialize(deserializer)?;
+
+        usize::try_from(words_count)
+            .ok()
+            .and_then(MnemonicType::from_words_count)
+            .ok_or_else(|| serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(words_count),
+                &"12, 15, 18, 21 or 24 words",
+            ))
+    }
 }
\ No newline at end of file
@@ -1,21 +1,38 @@
-use crate::{MnemonicType, Mnemonic};
-use hex;
+use crate::{MnemonicType, Mnemonic, MnemonicError};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use rand_core::{CryptoRng, RngCore};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
 
-pub fn prepare_data_for_mnemonic_struct_initialization(mnemonic_type: MnemonicType) -> (Vec<u8>, u8) {
-    let raw_entropy = Mnemonic::generate_entropy(mnemonic_type);
+pub fn prepare_data_for_mnemonic_struct_initialization<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    mnemonic_type: MnemonicType,
+) -> Result<(Vec<u8>, u8), MnemonicError> {
+    let raw_entropy = Mnemonic::generate_entropy(rng, mnemonic_type)?;
     let checksum_decimal = Mnemonic::generate_checksum(&raw_entropy, mnemonic_type);
 
-    (raw_entropy, checksum_decimal) //Return
+    Ok((raw_entropy, checksum_decimal)) //Return
 }
 
+#[deprecated(note = "phrases are no longer encoded through binary strings, this will be removed")]
 pub fn hex_to_binary(hex: &str) -> String {
-    // Convert hex string to bytes
-    let bytes = hex::decode(hex).expect("Invalid hex string");
+    // Convert each hex digit to 4 bits, rejecting what hex::decode rejected (odd length, non hex digits)
+    assert!(hex.len().is_multiple_of(2), "Invalid hex string");
 
-    // Convert each byte to a binary string and collect them
-    let binary_string: String = bytes.iter()
-        .map(|byte| format!("{:08b}", byte))  // Format each byte as 8-bit binary
-        .collect();
+    hex.chars()
+        .map(|digit| digit.to_digit(16).expect("Invalid hex string"))
+        .map(|nibble| format!("{:04b}", nibble))
+        .collect()
+}
+
+pub fn normalize(text: &str) -> Zeroizing<String> {
+    // BIP-39 requires NFKD for both phrase and passphrase, so NFC (Windows) and NFD (macOS) input resolve the same way
+    // The exact length is computed first, so the string never reallocates and leaves partial copies behind
+    let normalized_len = text.nfkd().map(char::len_utf8).sum();
+    let mut normalized = Zeroizing::new(String::with_capacity(normalized_len));
 
-    binary_string.to_string()
-}
\ No newline at end of file
+    normalized.extend(text.nfkd());
+    normalized
+}
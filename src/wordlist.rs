@@ -0,0 +1,315 @@
+use crate::{utils, Language};
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::path::Path;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+const WORDS_COUNT: usize = 2048;
+const PREFIX_LENGTH: usize = 4; // Number of letters that must identify every word
+
+/// Source of the 2048 words used to encode a mnemonic
+///
+/// Implemented by every built-in `Language` and by `CustomWordList`, which loads community or test wordlists.
+pub trait WordList: Send + Sync {
+    /// Returns the word at `index` (0 to 2047), or None if the index is out of range
+    fn word_at(&self, index: u16) -> Option<&str>;
+
+    /// Returns the index of `word`, or None if the word is not in the wordlist
+    fn index_of(&self, word: &str) -> Option<u16>;
+
+    /// Returns every word starting with `prefix`
+    fn complete<'a>(&'a self, prefix: &str) -> Box<dyn Iterator<Item = &'a str> + 'a>;
+
+    /// Separator placed between words when the phrase is displayed
+    fn separator(&self) -> &str {
+        " "
+    }
+
+    /// Normalization applied to a phrase before its words are looked up, NFKD as required by BIP-39
+    fn normalize(&self, text: &str) -> Zeroizing<String> {
+        utils::normalize(text)
+    }
+
+    /// Built-in language of this wordlist, None for custom wordlists
+    fn language(&self) -> Option<Language> {
+        None
+    }
+
+    /// Resolves a word or an unambiguous prefix of a word to its index, an exact match always wins
+    fn expand_prefix_index(&self, prefix: &str) -> Option<u16> {
+        if let Some(index) = self.index_of(prefix) {
+            return Some(index);
+        }
+
+        let mut completions = self.complete(prefix);
+        match (completions.next(), completions.next()) {
+            (Some(word), None) => self.index_of(word),
+            _ => None,
+        }
+    }
+}
+
+impl<T: WordList + ?Sized> WordList for Arc<T> {
+    fn word_at(&self, index: u16) -> Option<&str> {
+        (**self).word_at(index)
+    }
+
+    fn index_of(&self, word: &str) -> Option<u16> {
+        (**self).index_of(word)
+    }
+
+    fn complete<'a>(&'a self, prefix: &str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        (**self).complete(prefix)
+    }
+
+    fn separator(&self) -> &str {
+        (**self).separator()
+    }
+
+    fn normalize(&self, text: &str) -> Zeroizing<String> {
+        (**self).normalize(text)
+    }
+
+    fn language(&self) -> Option<Language> {
+        (**self).language()
+    }
+}
+
+impl<T: WordList + ?Sized> WordList for &T {
+    fn word_at(&self, index: u16) -> Option<&str> {
+        (**self).word_at(index)
+    }
+
+    fn index_of(&self, word: &str) -> Option<u16> {
+        (**self).index_of(word)
+    }
+
+    fn complete<'a>(&'a self, prefix: &str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        (**self).complete(prefix)
+    }
+
+    fn separator(&self) -> &str {
+        (**self).separator()
+    }
+
+    fn normalize(&self, text: &str) -> Zeroizing<String> {
+        (**self).normalize(text)
+    }
+
+    fn language(&self) -> Option<Language> {
+        (**self).language()
+    }
+}
+
+#[derive(Debug)]
+pub enum WordListError {
+    /// Wordlist doesn't contain exactly 2048 words
+    BadLength(usize),
+    /// Word on `line` (counted from 1) is empty or contains whitespace
+    InvalidWord { line: usize, word: String },
+    /// Word on `line` (counted from 1) already appears earlier in the wordlist
+    DuplicateWord { line: usize, word: String },
+    /// First four letters of the word on `line` (counted from 1) are shared with an earlier word
+    AmbiguousPrefix { line: usize, prefix: String },
+    /// Wordlist file couldn't be read
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl core::fmt::Display for WordListError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            WordListError::BadLength(count) => write!(f, "Wordlist must contain 2048 words, but got {}.", count),
+            WordListError::InvalidWord { line, word } => write!(f, "Invalid word \"{}\" on line {}.", word, line),
+            WordListError::DuplicateWord { line, word } => write!(f, "Duplicate word \"{}\" on line {}.", word, line),
+            WordListError::AmbiguousPrefix { line, prefix } => {
+                write!(f, "Prefix \"{}\" on line {} is shared with an earlier word.", prefix, line)
+            }
+            #[cfg(feature = "std")]
+            WordListError::Io(e) => write!(f, "Error when reading wordlist: {}.", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WordListError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WordListError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Wordlist loaded at runtime, e.g. the community Russian or Turkish lists or internal test lists
+///
+/// Words are stored in NFKD and checked for duplicates, count and uniqueness of their first four letters.
+pub struct CustomWordList {
+    words: Vec<String>,
+    sorted_indexes: Vec<u16>,
+    separator: String,
+}
+
+impl CustomWordList {
+    /// Loads a wordlist file with one word per line
+    #[cfg(feature = "std")]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<CustomWordList, WordListError> {
+        let contents = std::fs::read_to_string(path).map_err(WordListError::Io)?;
+        Self::parse(&contents)
+    }
+
+    /// Parses a wordlist with one word per line, surrounding whitespace and a trailing empty line are ignored
+    pub fn parse(contents: &str) -> Result<CustomWordList, WordListError> {
+        Self::from_words(contents.trim_end().lines().map(str::trim))
+    }
+
+    /// Builds a wordlist from exactly 2048 words, in index order
+    pub fn from_words<I, S>(words: I) -> Result<CustomWordList, WordListError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let words: Vec<String> = words.into_iter().map(|word| word.as_ref().nfkd().collect()).collect();
+
+        if words.len() != WORDS_COUNT {
+            return Err(WordListError::BadLength(words.len()));
+        }
+
+        let mut seen_words = BTreeSet::new();
+        let mut seen_prefixes = BTreeSet::new();
+
+        for (index, word) in words.iter().enumerate() {
+            let line = index + 1;
+
+            if word.is_empty() || word.chars().any(char::is_whitespace) {
+                return Err(WordListError::InvalidWord { line, word: word.clone() });
+            }
+            if !seen_words.insert(word.as_str()) {
+                return Err(WordListError::DuplicateWord { line, word: word.clone() });
+            }
+
+            // Letters are counted on the composed form, so an accented letter counts once
+            let prefix: String = word.nfc().take(PREFIX_LENGTH).collect();
+            if !seen_prefixes.insert(prefix.clone()) {
+                return Err(WordListError::AmbiguousPrefix { line, prefix });
+            }
+        }
+
+        let mut sorted_indexes: Vec<u16> = (0..WORDS_COUNT as u16).collect();
+        sorted_indexes.sort_by(|a, b| words[*a as usize].as_bytes().cmp(words[*b as usize].as_bytes()));
+
+        Ok(CustomWordList {
+            words,
+            sorted_indexes,
+            separator: String::from(" "),
+        })
+    }
+
+    /// Changes the separator placed between words when the phrase is displayed
+    pub fn with_separator(mut self, separator: &str) -> CustomWordList {
+        self.separator = String::from(separator);
+        self
+    }
+}
+
+impl WordList for CustomWordList {
+    fn word_at(&self, index: u16) -> Option<&str> {
+        self.words.get(index as usize).map(String::as_str)
+    }
+
+    fn index_of(&self, word: &str) -> Option<u16> {
+        let word = self.normalize(word);
+
+        self.sorted_indexes
+            .binary_search_by(|index| self.words[*index as usize].as_bytes().cmp(word.as_bytes()))
+            .ok()
+            .map(|position| self.sorted_indexes[position])
+    }
+
+    fn complete<'a>(&'a self, prefix: &str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        let prefix = self.normalize(prefix);
+        let word = |index: &u16| self.words[*index as usize].as_bytes();
+
+        // Words sharing a prefix are contiguous in byte order
+        let start = self.sorted_indexes.partition_point(|index| word(index) < prefix.as_bytes());
+        let end = self.sorted_indexes
+            .partition_point(|index| word(index) < prefix.as_bytes() || word(index).starts_with(prefix.as_bytes()));
+
+        Box::new(self.sorted_indexes[start..end].iter().map(move |index| self.words[*index as usize].as_str()))
+    }
+
+    fn separator(&self) -> &str {
+        &self.separator
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_words() -> Vec<String> {
+        // 2048 three letter words: "aaa", "aab", ... every word is its own four letter prefix
+        (0..WORDS_COUNT)
+            .map(|i| {
+                let letter = |n: usize| (b'a' + (n % 26) as u8) as char;
+                [letter(i / 676), letter(i / 26), letter(i)].iter().collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_custom_wordlist_lookup() {
+        let wordlist = CustomWordList::parse(&test_words().join("\n")).expect("Wordlist should be valid");
+
+        assert_eq!(wordlist.word_at(0), Some("aaa"));
+        assert_eq!(wordlist.index_of("aab"), Some(1));
+        assert_eq!(wordlist.index_of("zzz"), None);
+        assert_eq!(wordlist.word_at(2048), None);
+        assert_eq!(wordlist.complete("ab").count(), 26);
+        assert_eq!(wordlist.expand_prefix_index("cf"), None);
+        assert_eq!(wordlist.separator(), " ");
+        assert_eq!(wordlist.language(), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_custom_wordlist_from_file() {
+        let path = std::env::temp_dir().join(format!("bip39_rusty_wordlist_{}.txt", std::process::id()));
+        std::fs::write(&path, test_words().join("\n") + "\n").unwrap();
+
+        let wordlist = CustomWordList::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(wordlist.expect("Wordlist should be valid").index_of("aba"), Some(26));
+        assert!(matches!(CustomWordList::from_file(&path), Err(WordListError::Io(_))));
+    }
+
+    #[test]
+    fn test_invalid_custom_wordlists() {
+        let mut words = test_words();
+        words.pop();
+        assert!(matches!(CustomWordList::from_words(&words), Err(WordListError::BadLength(2047))));
+
+        let mut words = test_words();
+        words[10] = String::from("aaa");
+        assert!(matches!(CustomWordList::from_words(&words), Err(WordListError::DuplicateWord { line: 11, .. })));
+
+        let mut words = test_words();
+        words[0] = String::from("abcdx");
+        words[1] = String::from("abcdy");
+        assert!(matches!(
+            CustomWordList::from_words(&words),
+            Err(WordListError::AmbiguousPrefix { line: 2, prefix }) if prefix == "abcd"
+        ));
+
+        let mut words = test_words();
+        words[5] = String::from("two words");
+        assert!(matches!(CustomWordList::from_words(&words), Err(WordListError::InvalidWord { line: 6, .. })));
+    }
+}
@@ -0,0 +1,58 @@
+// Runs in its own test binary because it installs a global allocator that counts allocations
+use bip39_rusty::{Language, MnemonicIndices};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Allocator counting the allocations made by the current thread, other test threads don't interfere
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn test_fixed_size_mnemonic_never_allocates() {
+    let mut rng = ChaCha20Rng::from_seed([7u8; 32]);
+    let mut rendered = [0u8; 256];
+
+    let before = allocations();
+
+    let generated = MnemonicIndices::<24>::generate_with_rng(&mut rng, &Language::English).unwrap();
+    assert!(generated.validate_checksum());
+
+    // Render word by word into a stack buffer, then parse it back
+    let mut length = 0;
+    for word in generated.words() {
+        rendered[length..length + word.len()].copy_from_slice(word.as_bytes());
+        rendered[length + word.len()] = b' ';
+        length += word.len() + 1;
+    }
+    let phrase = std::str::from_utf8(&rendered[..length - 1]).unwrap();
+
+    let parsed = MnemonicIndices::<24>::from_phrase(phrase, &Language::English).unwrap();
+    let from_entropy = MnemonicIndices::<12>::from_entropy(&[0x7f; 16], &Language::English).unwrap();
+
+    assert_eq!(allocations(), before, "MnemonicIndices should not allocate");
+    assert_eq!(parsed.indices(), generated.indices());
+    assert_eq!(from_entropy.words().last(), Some("yellow"));
+}
@@ -0,0 +1,39 @@
+/*
+    Builds the library without the default `std` feature for a bare-metal Cortex-M4F target,
+    so any std-only item leaking into the no_std + alloc build fails the test suite.
+    The test fails when the target isn't installed: `rustup target add thumbv7em-none-eabihf`
+*/
+
+use std::path::Path;
+use std::process::Command;
+
+const TARGET: &str = "thumbv7em-none-eabihf";
+
+fn target_installed() -> bool {
+    let output = Command::new("rustc").args(["--print", "sysroot"]).output();
+
+    match output {
+        Ok(output) => {
+            let sysroot = String::from_utf8_lossy(&output.stdout);
+            Path::new(sysroot.trim()).join("lib/rustlib").join(TARGET).exists()
+        }
+        Err(_) => false,
+    }
+}
+
+#[test]
+fn test_builds_for_thumbv7em_without_std() {
+    assert!(target_installed(), "Target {} is not installed, run `rustup target add {}`", TARGET, TARGET);
+
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+
+    // Separate target directory, the one of the running `cargo test` is locked
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--lib", "--no-default-features", "--target", TARGET])
+        .current_dir(manifest_dir)
+        .env("CARGO_TARGET_DIR", Path::new(manifest_dir).join("target").join("no_std"))
+        .status()
+        .expect("Cargo should run");
+
+    assert!(status.success(), "Library should build for {} without the std feature", TARGET);
+}
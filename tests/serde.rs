@@ -0,0 +1,100 @@
+// Requires the `serde` feature: cargo test --features serde
+use bip39_rusty::{Language, Mnemonic, MnemonicType};
+use serde::{Deserialize, Serialize};
+
+const PHRASE: &str = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+
+#[derive(Serialize, Deserialize)]
+struct WalletConfig {
+    mnemonic: Mnemonic,
+    language: Language,
+    mnemonic_type: MnemonicType,
+}
+
+fn wallet_config() -> WalletConfig {
+    WalletConfig {
+        mnemonic: Mnemonic::from_phrase(PHRASE, Language::English).unwrap(),
+        language: Language::ChineseTraditional,
+        mnemonic_type: MnemonicType::Bits256,
+    }
+}
+
+#[test]
+fn test_json_round_trip() {
+    let json = serde_json::to_string(&wallet_config()).unwrap();
+    assert_eq!(json, format!(r#"{{"mnemonic":"{}","language":"zh-Hant","mnemonic_type":24}}"#, PHRASE));
+
+    let config: WalletConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(config.mnemonic.entropy(), &[0x7f; 16]);
+    assert_eq!(config.language, Language::ChineseTraditional);
+    assert_eq!(config.mnemonic_type, MnemonicType::Bits256);
+}
+
+#[test]
+fn test_toml_round_trip() {
+    let toml = toml::to_string(&wallet_config()).unwrap();
+    assert!(toml.contains("language = \"zh-Hant\""));
+    assert!(toml.contains("mnemonic_type = 24"));
+
+    let config: WalletConfig = toml::from_str(&toml).unwrap();
+    assert_eq!(config.mnemonic.mnemonic_phrase().join(" "), PHRASE);
+    assert_eq!(config.language, Language::ChineseTraditional);
+}
+
+#[test]
+fn test_every_language_round_trip() {
+    for language in Language::ALL {
+        let mnemonic = Mnemonic::from_entropy(&[0x7f; 32], language).unwrap();
+
+        let json = serde_json::to_string(&(language, &mnemonic)).unwrap();
+        let (parsed_language, parsed): (Language, Mnemonic) = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed_language, language);
+        assert_eq!(parsed.entropy(), mnemonic.entropy());
+    }
+}
+
+#[test]
+fn test_phrase_shared_by_chinese_wordlists_round_trip() {
+    // "的 的 的 的 的 的 的 的 的 的 的 在", the same characters at the same indexes in both Chinese wordlists
+    let entropy = [0u8; 16];
+
+    for language in [Language::ChineseSimplified, Language::ChineseTraditional] {
+        let mnemonic = Mnemonic::from_entropy(&entropy, language).unwrap();
+        assert!(Language::detect(&mnemonic.phrase()).is_err());
+
+        let json = serde_json::to_string(&mnemonic).unwrap();
+        let parsed: Mnemonic = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.entropy(), &entropy);
+        assert_eq!(*parsed.to_seed("TREZOR"), *mnemonic.to_seed("TREZOR"));
+    }
+}
+
+#[test]
+fn test_phrase_matching_other_wordlist_prefixes_round_trip() {
+    // Also valid in French when "age" and "bid" are expanded to "agencer" and "bidule"
+    let mnemonic = Mnemonic::from_phrase(
+        "abandon age arrive bid bullet carbon civil correct cycle divert dress box",
+        Language::English,
+    )
+    .unwrap();
+
+    let json = serde_json::to_string(&mnemonic).unwrap();
+    let parsed: Mnemonic = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(parsed.entropy(), mnemonic.entropy());
+}
+
+#[test]
+fn test_invalid_values_are_rejected() {
+    let tampered = PHRASE.replace("yellow", "year");
+    let error = serde_json::from_str::<Mnemonic>(&format!("\"{}\"", tampered)).err().unwrap();
+    assert!(error.to_string().contains("Invalid checksum"));
+
+    assert!(serde_json::from_str::<Mnemonic>("\"legal winner\"").is_err());
+    assert!(serde_json::from_str::<Mnemonic>("\"lega winn than year wave saus wort usef lega winn than yell\"").is_err());
+    assert!(serde_json::from_str::<Language>("\"xx\"").is_err());
+    assert!(serde_json::from_str::<MnemonicType>("13").is_err());
+    assert_eq!(serde_json::from_str::<Language>("\"ZH-HANS\"").unwrap(), Language::ChineseSimplified);
+}
@@ -0,0 +1,84 @@
+// Runs in its own test binary because it installs a global allocator that inspects every freed buffer
+use bip39_rusty::{Language, Mnemonic};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+const ENTROPY: [u8; 16] = [
+    0x9e, 0x88, 0x5d, 0x95, 0x2a, 0xd3, 0x62, 0xca, 0xeb, 0x4e, 0xfe, 0x34, 0xa8, 0xe9, 0x1b, 0xd2,
+];
+const PHRASE: &str = "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic";
+const BINARY_PREFIX: &str = "100111101000100001011101"; // first 3 bytes of ENTROPY as '0'/'1' characters
+const SEED_PREFIX: [u8; 16] = [
+    0x27, 0x4d, 0xdc, 0x52, 0x58, 0x02, 0xf7, 0xc8, 0x28, 0xd8, 0xef, 0x7d, 0xdb, 0xcd, 0xc5, 0x30,
+];
+
+const SECRETS: [&[u8]; 13] = [
+    &ENTROPY,
+    BINARY_PREFIX.as_bytes(),
+    &SEED_PREFIX,
+    b"ozone",
+    b"drill",
+    b"fiber",
+    b"curtain",
+    b"grace",
+    b"pudding",
+    b"thank",
+    b"cruise",
+    b"elder",
+    b"picnic",
+];
+
+static SCANNING: AtomicBool = AtomicBool::new(false);
+static LEAKS: [AtomicUsize; SECRETS.len()] = [const { AtomicUsize::new(0) }; SECRETS.len()];
+
+/// Allocator that looks for secret material in every buffer right before it is returned to the system
+struct ScanningAllocator;
+
+unsafe impl GlobalAlloc for ScanningAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if SCANNING.load(Ordering::SeqCst) {
+            let freed = std::slice::from_raw_parts(ptr, layout.size());
+
+            for (secret, leaks) in SECRETS.iter().zip(LEAKS.iter()) {
+                if freed.windows(secret.len()).any(|window| window == *secret) {
+                    leaks.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        }
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: ScanningAllocator = ScanningAllocator;
+
+#[test]
+fn test_freed_buffers_contain_no_secrets() {
+    SCANNING.store(true, Ordering::SeqCst);
+
+    let mnemonic = Mnemonic::from_entropy(&ENTROPY, Language::English).expect("Entropy should be valid");
+    let seed = mnemonic.to_seed("TREZOR");
+    let seed_matches = seed[..16] == SEED_PREFIX;
+    drop(seed);
+    drop(mnemonic);
+
+    let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).expect("Phrase should be valid");
+    let checksum_valid = mnemonic.validate_checksum().expect("Phrase should be decodable");
+    drop(mnemonic);
+
+    SCANNING.store(false, Ordering::SeqCst);
+
+    assert!(seed_matches, "Seed should match the Trezor test vector");
+    assert!(checksum_valid, "Checksum should be valid");
+
+    for (secret, leaks) in SECRETS.iter().zip(LEAKS.iter()) {
+        assert_eq!(
+            leaks.load(Ordering::SeqCst), 0,
+            "Freed buffer still contained secret {:?}", String::from_utf8_lossy(secret)
+        );
+    }
+}